@@ -1,44 +1,13 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::time::Instant;
 
-use itertools::Itertools;
+use hymns::graph::{held_karp, DistanceMatrix, Objective, Route};
 
 const INPUT: &str = include_str!("../input.txt");
 
-struct Distances {
-    distances: HashMap<(String, String), u64>,
-    all_cities: HashSet<String>,
-}
-
-impl Distances {
-    fn new() -> Self {
-        Self {
-            distances: HashMap::new(),
-            all_cities: HashSet::new(),
-        }
-    }
-
-    fn make_key(src: &str, dst: &str) -> (String, String) {
-        if src < dst {
-            (src.to_owned(), dst.to_owned())
-        } else {
-            (dst.to_owned(), src.to_owned())
-        }
-    }
-
-    fn set_distance(&mut self, src: &str, dst: &str, distance: u64) {
-        self.all_cities.insert(src.to_owned());
-        self.all_cities.insert(dst.to_owned());
-        self.distances.insert(Self::make_key(src, dst), distance);
-    }
-
-    fn get_distance(&self, src: &str, dst: &str) -> Option<u64> {
-        self.distances.get(&Self::make_key(src, dst)).copied()
-    }
-}
-
-fn build_distances() -> Distances {
-    let mut distances = Distances::new();
+fn build_distances() -> DistanceMatrix<u64> {
+    let mut cities = HashMap::new();
+    let mut routes = vec![];
 
     for line in INPUT.lines() {
         let mut split = line.split_whitespace();
@@ -49,35 +18,33 @@ fn build_distances() -> Distances {
         split.next(); // =
         let distance = split.next().unwrap().parse().unwrap();
 
-        distances.set_distance(src, dst, distance);
+        let next_id = cities.len();
+        let src = *cities.entry(src).or_insert(next_id);
+        let next_id = cities.len();
+        let dst = *cities.entry(dst).or_insert(next_id);
+
+        routes.push((src, dst, distance));
+    }
+
+    let mut distances = DistanceMatrix::new(cities.len());
+    for (src, dst, distance) in routes {
+        distances.set_symmetric(src, dst, distance);
     }
 
     distances
 }
 
-fn all_path_lengths(distances: &Distances) -> impl Iterator<Item = u64> + '_ {
-    distances
-        .all_cities
-        .iter()
-        .permutations(distances.all_cities.len())
-        .filter_map(|permutation| {
-            permutation
-                .iter()
-                .tuple_windows()
-                .try_fold(0, |acc, (src, dst)| {
-                    distances.get_distance(src, dst).map(|d| acc + d)
-                })
-        })
+fn best_route(objective: Objective) -> u64 {
+    let distances = build_distances();
+    held_karp(&distances, objective, Route::Path).unwrap().0
 }
 
 fn part1() -> u64 {
-    let distances = build_distances();
-    all_path_lengths(&distances).min().unwrap()
+    best_route(Objective::Min)
 }
 
 fn part2() -> u64 {
-    let distances = build_distances();
-    all_path_lengths(&distances).max().unwrap()
+    best_route(Objective::Max)
 }
 
 fn main() {
@@ -1,12 +1,15 @@
 use std::collections::HashMap;
 use std::time::Instant;
 
-use itertools::Itertools;
+use hymns::graph::{held_karp, Matrix, Objective, Route};
 
 const INPUT: &str = include_str!("../input.txt");
 
-fn build_happiness_map() -> HashMap<&'static str, HashMap<&'static str, i64>> {
-    let mut happiness_map: HashMap<&'static str, HashMap<&'static str, i64>> = HashMap::new();
+// The happiness each pair of guests gains from sitting together, counting both of
+// them. With `include_self`, one extra guest who doesn't mind anyone is seated too.
+fn build_happiness_matrix(include_self: bool) -> Matrix<i64> {
+    let mut guests = HashMap::new();
+    let mut feelings = vec![];
 
     for line in INPUT.lines() {
         let words: Vec<_> = line.split_ascii_whitespace().collect();
@@ -19,83 +22,35 @@ fn build_happiness_map() -> HashMap<&'static str, HashMap<&'static str, i64>> {
             amount = -amount;
         }
 
-        happiness_map
-            .entry(recipient)
-            .or_default()
-            .insert(giver, amount);
-    }
-
-    happiness_map
-}
-
-fn part1() -> i64 {
-    let happiness_map = build_happiness_map();
-
-    let person_count = happiness_map.len();
-
-    let mut max_happiness = 0;
+        let next_id = guests.len();
+        let recipient = *guests.entry(recipient).or_insert(next_id);
+        let next_id = guests.len();
+        let giver = *guests.entry(giver).or_insert(next_id);
 
-    for permutation in happiness_map.keys().permutations(person_count) {
-        let mut cur_happiness = 0;
-
-        for i in 0..person_count {
-            let neighbors = if i == 0 {
-                [1, person_count - 1]
-            } else if i == person_count - 1 {
-                [person_count - 2, 0]
-            } else {
-                [i - 1, i + 1]
-            };
-
-            let cur_person = permutation[i];
-            let first = permutation[neighbors[0]];
-            let second = permutation[neighbors[1]];
+        feelings.push((recipient, giver, amount));
+    }
 
-            cur_happiness += happiness_map[cur_person][first] + happiness_map[cur_person][second];
-        }
+    let guest_count = guests.len() + usize::from(include_self);
 
-        max_happiness = max_happiness.max(cur_happiness);
+    let mut happiness = Matrix::new(guest_count);
+    for (recipient, giver, amount) in feelings {
+        let total = happiness.get(recipient, giver) + amount;
+        happiness.set_symmetric(recipient, giver, total);
     }
 
-    max_happiness
+    happiness
 }
 
-fn part2() -> i64 {
-    let happiness_map = build_happiness_map();
-
-    let mut max_happiness = 0;
-
-    let mut all_people: Vec<_> = happiness_map.keys().copied().collect();
-    all_people.push("self");
-
-    let person_count = all_people.len();
-
-    for permutation in all_people.iter().permutations(all_people.len()) {
-        let mut cur_happiness = 0;
-
-        for i in 0..person_count {
-            let neighbors = if i == 0 {
-                [1, person_count - 1]
-            } else if i == person_count - 1 {
-                [person_count - 2, 0]
-            } else {
-                [i - 1, i + 1]
-            };
-
-            let cur_person = permutation[i];
-            let first = permutation[neighbors[0]];
-            let second = permutation[neighbors[1]];
-
-            if let Some(map_for_person) = happiness_map.get(cur_person) {
-                cur_happiness += map_for_person.get(first).unwrap_or(&0)
-                    + map_for_person.get(second).unwrap_or(&0);
-            }
-        }
+fn max_happiness(happiness: &Matrix<i64>) -> i64 {
+    held_karp(happiness, Objective::Max, Route::Tour).unwrap().0
+}
 
-        max_happiness = max_happiness.max(cur_happiness);
-    }
+fn part1() -> i64 {
+    max_happiness(&build_happiness_matrix(false))
+}
 
-    max_happiness
+fn part2() -> i64 {
+    max_happiness(&build_happiness_matrix(true))
 }
 
 fn main() {
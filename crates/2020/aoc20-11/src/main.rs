@@ -1,103 +1,72 @@
 use std::time::Instant;
 
-const INPUT: &str = include_str!("../input.txt");
-const OFFSETS: [(isize, isize); 8] = [
-    (-1, -1),
-    (-1, 0),
-    (-1, 1),
-    (0, -1),
-    (0, 1),
-    (1, -1),
-    (1, 0),
-    (1, 1),
-];
-
-type GridRow = Vec<char>;
-type Grid = Vec<GridRow>;
-
-fn read_grid() -> Grid {
-    INPUT.lines().map(|line| line.chars().collect()).collect()
-}
+use hymns::automaton::GridAutomaton;
+use hymns::grid::{GPoint, Grid};
+use hymns::p2;
+use hymns::vector2::{Point2, EIGHT_NEIGHBORS};
 
-#[allow(clippy::needless_pass_by_value)]
-fn step(grid: Grid, extended: bool) -> (Grid, bool) {
-    let mut new_grid = grid.clone();
-    let mut changed = false;
+const INPUT: &str = include_str!("../input.txt");
 
-    let neighbor_limit = if extended { 5 } else { 4 };
+fn read_grid() -> Grid<char> {
+    INPUT.parse().unwrap()
+}
 
-    for (r, row) in grid.iter().enumerate() {
-        for (c, item) in row.iter().enumerate() {
-            if matches!(item, 'L' | '#') {
-                let neighbors = count_neighbors(&grid, r, c, extended);
-
-                if *item == 'L' {
-                    if neighbors == 0 {
-                        new_grid[r][c] = '#';
-                        changed = true;
-                    }
-                } else if neighbors >= neighbor_limit {
-                    new_grid[r][c] = 'L';
-                    changed = true;
+// The seats each seat can see: the adjacent ones, or with `extended` the first seat in
+// each direction. Seats never move, so this only has to be worked out once.
+fn visible_seats(grid: &Grid<char>, extended: bool) -> Grid<Vec<GPoint>> {
+    let seats_from = |p: GPoint| {
+        EIGHT_NEIGHBORS
+            .iter()
+            .filter_map(|&direction| {
+                if extended {
+                    grid.first_in_direction(p, direction, |&c| c != '.')
+                        .map(|(seat, _)| seat)
+                } else {
+                    grid.get_neighbor(&p, direction)
+                        .filter(|seat| grid[seat] != '.')
                 }
-            }
-        }
-    }
-
-    (new_grid, changed)
+            })
+            .collect()
+    };
+
+    Grid::new(
+        (0..grid.rows())
+            .map(|y| (0..grid.cols()).map(|x| seats_from(p2!(x, y))).collect())
+            .collect(),
+    )
 }
 
-#[allow(clippy::cast_sign_loss, clippy::cast_possible_wrap)]
-fn count_neighbors(grid: &[GridRow], r: usize, c: usize, extended: bool) -> u64 {
-    let mut total = 0;
-
-    for (row_off, col_off) in &OFFSETS {
-        let mut dr = *row_off;
-        let mut dc = *col_off;
+fn get_neighbors_at_steady_state(extended: bool) -> usize {
+    let neighbor_limit = if extended { 5 } else { 4 };
 
-        loop {
-            let new_r = ((r as isize) + dr) as usize;
-            let new_c = ((c as isize) + dc) as usize;
+    let grid = read_grid();
+    let visible = visible_seats(&grid, extended);
 
-            match grid.get(new_r).and_then(|row| row.get(new_c)) {
-                Some('#') => {
-                    total += 1;
-                    break;
-                }
-                Some('L') | None => {
-                    break;
-                }
-                Some('.') => (),
-                _ => unreachable!(),
-            }
-
-            if extended {
-                dr += row_off;
-                dc += col_off;
-            } else {
-                break;
-            }
+    // the rule reads the precomputed seats, so the automaton needs no neighborhood
+    let mut automaton = GridAutomaton::new(grid, &[], |view| {
+        if *view.value == '.' {
+            return '.';
         }
-    }
-
-    total
-}
-
-fn get_neighbors_at_steady_state(extended: bool) -> usize {
-    let mut grid = read_grid();
 
-    loop {
-        let (new, changed) = step(grid, extended);
+        let occupied = visible[view.point]
+            .iter()
+            .filter(|&seat| view.grid()[seat] == '#')
+            .count();
 
-        if !changed {
-            return new
-                .iter()
-                .map(|row| row.iter().filter(|c| **c == '#').count())
-                .sum();
+        match view.value {
+            'L' if occupied == 0 => '#',
+            '#' if occupied >= neighbor_limit => 'L',
+            &c => c,
         }
+    });
 
-        grid = new;
-    }
+    automaton.run_until_stable();
+
+    automaton
+        .grid()
+        .iter_values()
+        .filter(|&&c| c == '#')
+        .count()
 }
 
 fn part1() -> usize {
@@ -1,24 +1,14 @@
 use std::collections::{HashMap, HashSet};
 use std::time::Instant;
 
+use hymns::grid::Grid;
+
 const INPUT: &str = include_str!("../input.txt");
 
-const MONSTER_COORDS: [(usize, usize); 15] = [
-    (0, 18),
-    (1, 0),
-    (1, 5),
-    (1, 6),
-    (1, 11),
-    (1, 12),
-    (1, 17),
-    (1, 18),
-    (1, 19),
-    (2, 1),
-    (2, 4),
-    (2, 7),
-    (2, 10),
-    (2, 13),
-    (2, 16),
+const MONSTER: [&str; 3] = [
+    "                  # ",
+    "#    ##    ##    ###",
+    " #  #  #  #  #  #   ",
 ];
 
 #[derive(Copy, Clone)]
@@ -83,7 +73,9 @@ impl Tile {
 
         let mut reversed_edges = edges.clone();
 
-        reversed_edges.iter_mut().for_each(|edge| edge.reverse());
+        for edge in &mut reversed_edges {
+            edge.reverse();
+        }
 
         edges.append(&mut reversed_edges);
         edges
@@ -119,49 +111,6 @@ impl Tile {
             .map(|row| row[1..nc - 1].to_vec())
             .collect();
     }
-
-    fn mark_monster_at_offset(&mut self, r: usize, c: usize) -> bool {
-        let hash_count = MONSTER_COORDS
-            .iter()
-            .take_while(|(x, y)| self.data.get(r + x).and_then(|row| row.get(c + y)) == Some(&'#'))
-            .count();
-
-        if hash_count == MONSTER_COORDS.len() {
-            for (x, y) in &MONSTER_COORDS {
-                self.data[r + x][c + y] = 'O';
-            }
-            true
-        } else {
-            false
-        }
-    }
-
-    fn find_and_mark_monsters(&mut self) {
-        let mut found = false;
-
-        for _ in 0..9 {
-            for row in 0..self.data.len() {
-                for col in 0..self.data[row].len() {
-                    if self.mark_monster_at_offset(row, col) {
-                        found = true;
-                    }
-                }
-            }
-
-            if found {
-                return;
-            }
-            self.transform();
-        }
-    }
-
-    fn count_hashes(&self) -> usize {
-        self.data
-            .iter()
-            .flat_map(|row| row.iter())
-            .filter(|&c| c == &'#')
-            .count()
-    }
 }
 
 fn make_tiles() -> Vec<Tile> {
@@ -236,10 +185,10 @@ fn find_top_left(tiles: &mut [Tile]) -> usize {
 
                 let is_top_unique = border_to_tile_map
                     .get(&top)
-                    .map_or(false, |ids| ids.len() == 1);
+                    .is_some_and(|ids| ids.len() == 1);
                 let is_left_unique = border_to_tile_map
                     .get(&left)
-                    .map_or(false, |ids| ids.len() == 1);
+                    .is_some_and(|ids| ids.len() == 1);
                 if is_top_unique && is_left_unique {
                     return true;
                 }
@@ -295,11 +244,28 @@ fn part2() -> usize {
         }
     }
 
-    let mut big_tile = combine_tiles(image);
+    let big_tile = combine_tiles(image);
 
-    big_tile.find_and_mark_monsters();
+    water_roughness(&Grid::new(big_tile.data))
+}
+
+fn water_roughness(image: &Grid<char>) -> usize {
+    let monster = Grid::new(MONSTER.iter().map(|line| line.chars().collect()).collect());
+
+    let mut monster_cells = HashSet::new();
+
+    for (symmetry, offset) in image.find_pattern_oriented(&monster, &' ') {
+        let oriented = monster.transformed(symmetry);
+
+        monster_cells.extend(
+            oriented
+                .iter_points_values()
+                .filter(|(_, &c)| c == '#')
+                .map(|(p, _)| p + offset),
+        );
+    }
 
-    big_tile.count_hashes()
+    image.iter_values().filter(|&&c| c == '#').count() - monster_cells.len()
 }
 
 fn combine_tiles(mut tiles: Vec<Vec<Tile>>) -> Tile {
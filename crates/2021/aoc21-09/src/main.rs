@@ -1,5 +1,4 @@
 use std::cmp::Reverse;
-use std::collections::{HashSet, VecDeque};
 
 use hymns::grid::{GPoint, Grid};
 use hymns::runner::timed_run;
@@ -22,25 +21,6 @@ fn find_low_points(grid: &Grid<u8>) -> Vec<GPoint> {
         .collect()
 }
 
-fn trace_basin(grid: &Grid<u8>, start: &GPoint) -> usize {
-    let mut seen = HashSet::new();
-
-    let mut frontier = VecDeque::new();
-    frontier.push_front(*start);
-
-    while let Some(current) = frontier.pop_front() {
-        seen.insert(current);
-
-        for neighbor in grid.all_neighbors(&current, false) {
-            if grid[neighbor] != 9 && !seen.contains(&neighbor) {
-                frontier.push_back(neighbor);
-            }
-        }
-    }
-
-    seen.len()
-}
-
 fn build_grid() -> Grid<u8> {
     let map: Vec<Vec<u8>> = INPUT
         .lines()
@@ -66,15 +46,9 @@ fn part1() -> u32 {
 fn part2() -> usize {
     let grid = build_grid();
 
-    let mut sizes = vec![];
-
-    for p in find_low_points(&grid) {
-        let mut seen = HashSet::new();
-        seen.insert(p);
-
-        sizes.push(trace_basin(&grid, &p));
-    }
+    let basins = grid.components(|&height| height != 9, false);
 
+    let mut sizes = basins.sizes().collect::<Vec<_>>();
     sizes.sort_unstable_by_key(|&n| Reverse(n));
 
     sizes.into_iter().take(3).product()
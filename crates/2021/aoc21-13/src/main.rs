@@ -1,3 +1,4 @@
+use hymns::ocr;
 use hymns::p2;
 use hymns::runner::timed_run;
 use hymns::vector2::Point2;
@@ -76,32 +77,7 @@ fn part2() -> String {
         reflect_points(reflection_axis, x_or_y, &mut points);
     }
 
-    let (min_x, max_x, min_y, max_y) = points.iter().fold(
-        (usize::MAX, usize::MIN, usize::MAX, usize::MIN),
-        |(min_x, max_x, min_y, max_y), p| {
-            (
-                min_x.min(p.x),
-                max_x.max(p.x),
-                min_y.min(p.y),
-                max_y.max(p.y),
-            )
-        },
-    );
-
-    let mut result = String::new();
-
-    for y in min_y..=max_y {
-        for x in min_x..=max_x {
-            if points.contains(&p2!(x, y)) {
-                result.push('#');
-            } else {
-                result.push(' ');
-            }
-        }
-        result.push('\n');
-    }
-
-    result
+    ocr::recognize_points(&points).unwrap()
 }
 
 fn main() {
@@ -120,16 +96,6 @@ mod tests {
 
     #[test]
     fn test_part2() {
-        let mut expected = r"###  #### #  # ###   ##    ## #### #  #
-#  # #    # #  #  # #  #    #    # #  #
-###  ###  ##   #  # #       #   #  #  #
-#  # #    # #  ###  #       #  #   #  #
-#  # #    # #  # #  #  # #  # #    #  #
-###  #    #  # #  #  ##   ##  ####  ## "
-            .to_string();
-        // This is just to avoid ending a source code line with whitespace
-        expected.push('\n');
-
-        assert_eq!(part2(), expected);
+        assert_eq!(part2(), "BFKRCJZU");
     }
 }
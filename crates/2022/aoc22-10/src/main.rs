@@ -1,5 +1,5 @@
-extern crate core;
-
+use hymns::grid::Grid;
+use hymns::ocr;
 use hymns::runner::timed_run;
 
 const INPUT: &str = include_str!("../input.txt");
@@ -21,7 +21,7 @@ fn part1() -> i64 {
 
         for _ in 0..execution_time {
             cycle_count += 1;
-            if cycle_count == 20 || cycle_count.checked_sub(20).map_or(false, |n| n % 40 == 0) {
+            if cycle_count == 20 || cycle_count.checked_sub(20).is_some_and(|n| n % 40 == 0) {
                 result += i64::try_from(cycle_count).unwrap() * register;
             }
         }
@@ -33,7 +33,7 @@ fn part1() -> i64 {
 }
 
 fn part2() -> String {
-    let mut screen = vec![vec!["."; 40]; 6];
+    let mut screen = vec![vec![false; 40]; 6];
     let mut register: i64 = 1;
     let mut cycle_count: usize = 0;
 
@@ -52,7 +52,7 @@ fn part2() -> String {
             let col = cycle_count % 40;
 
             if (register - 1..=register + 1).contains(&col.try_into().unwrap()) {
-                screen[row][col] = "#";
+                screen[row][col] = true;
             }
 
             cycle_count += 1;
@@ -61,14 +61,7 @@ fn part2() -> String {
         register += add;
     }
 
-    screen
-        .into_iter()
-        .map(|row| {
-            let mut row_str = row.into_iter().collect::<String>();
-            row_str.push('\n');
-            row_str
-        })
-        .collect()
+    ocr::recognize(&Grid::new(screen)).unwrap()
 }
 
 fn main() {
@@ -87,12 +80,6 @@ mod tests {
 
     #[test]
     fn test_part2() {
-        let expected = "####...##.#..#.###..#..#.#....###..####.
-#.......#.#..#.#..#.#..#.#....#..#....#.
-###.....#.#..#.###..#..#.#....#..#...#..
-#.......#.#..#.#..#.#..#.#....###...#...
-#....#..#.#..#.#..#.#..#.#....#.#..#....
-#.....##...##..###...##..####.#..#.####.\n";
-        assert_eq!(part2(), expected);
+        assert_eq!(part2(), "FJUBULRZ");
     }
 }
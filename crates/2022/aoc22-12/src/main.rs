@@ -1,94 +1,32 @@
-use std::collections::{HashSet, VecDeque};
-
 use hymns::grid::{GPoint, Grid};
-use hymns::p2;
 use hymns::runner::timed_run;
-use hymns::vector2::Point2;
 const INPUT: &str = include_str!("../input.txt");
 
-fn build_grid(start: &mut GPoint, end: &mut GPoint) -> Grid<u8> {
-    Grid::new(
-        INPUT
-            .lines()
-            .enumerate()
-            .map(|(y, line)| {
-                line.as_bytes()
-                    .iter()
-                    .enumerate()
-                    .map(|(x, &elev)| match elev {
-                        b'S' => {
-                            *start = p2!(x, y);
-                            b'a'
-                        }
-                        b'E' => {
-                            *end = p2!(x, y);
-                            b'z'
-                        }
-                        _ => elev,
-                    })
-                    .collect()
-            })
-            .collect(),
-    )
-}
-
-fn part1() -> u64 {
-    let mut start = Point2::default();
-    let mut end = Point2::default();
-
-    let grid = build_grid(&mut start, &mut end);
-
-    let mut queue = VecDeque::new();
-    queue.push_back((0, start));
-
-    let mut seen = HashSet::new();
-
-    while let Some((distance, point)) = queue.pop_front() {
-        if point == end {
-            return distance;
-        }
-
-        if !seen.insert(point) {
-            continue;
-        }
+fn build_grid() -> (Grid<u8>, GPoint, GPoint) {
+    let (grid, markers) = Grid::parse_with_markers(INPUT, &[('S', 'a'), ('E', 'z')]).unwrap();
 
-        for neighbor in grid.all_neighbors(&point, false) {
-            if grid[neighbor] <= grid[point] + 1 {
-                queue.push_back((distance + 1, neighbor));
-            }
-        }
-    }
-
-    unreachable!()
+    (grid, markers[&'S'][0], markers[&'E'][0])
 }
 
-fn part2() -> u64 {
-    let mut start = Point2::default();
-
-    let grid = build_grid(&mut Point2::default(), &mut start);
-
-    let mut queue = VecDeque::new();
-    queue.push_back((0, start));
+fn part1() -> usize {
+    let (grid, start, end) = build_grid();
 
-    let mut seen = HashSet::new();
-
-    while let Some((distance, point)) = queue.pop_front() {
-        if grid[point] == b'a' {
-            return distance;
-        }
+    grid.distance_map_from(&[start], |from, to| grid[to] <= grid[from] + 1)
+        .distance(&end)
+        .unwrap()
+}
 
-        if !seen.insert(point) {
-            continue;
-        }
+fn part2() -> usize {
+    let (grid, _, end) = build_grid();
 
-        for neighbor in grid.all_neighbors(&point, false) {
-            if grid[point] <= grid[neighbor] + 1 {
-                queue.push_back((distance + 1, neighbor));
-            }
-        }
-    }
+    let starts = grid
+        .iter_points_values()
+        .filter_map(|(p, &elev)| (elev == b'a').then_some(p))
+        .collect::<Vec<_>>();
 
-    unreachable!()
+    grid.distance_map_from(&starts, |from, to| grid[to] <= grid[from] + 1)
+        .distance(&end)
+        .unwrap()
 }
 
 fn main() {
@@ -1,21 +1,18 @@
-use std::collections::HashSet;
-
 use itertools::Itertools;
 
+use hymns::grid::{Grid, SPoint};
 use hymns::p2;
 use hymns::runner::timed_run;
 use hymns::vector2::Point2;
 
 const INPUT: &str = include_str!("../input.txt");
 
-type Point = Point2<isize>;
-
-fn generate_points(s: &str) -> impl Iterator<Item = Point> + '_ {
+fn generate_points(s: &str) -> impl Iterator<Item = SPoint> + '_ {
     s.split(" -> ")
         .map(|p_str| {
             let (x, y) = p_str.split(',').collect_tuple().unwrap();
 
-            Point::new(x.parse().unwrap(), y.parse().unwrap())
+            SPoint::new(x.parse().unwrap(), y.parse().unwrap())
         })
         .tuple_windows()
         .flat_map(|(p1, p2)| {
@@ -40,15 +37,27 @@ fn generate_points(s: &str) -> impl Iterator<Item = Point> + '_ {
         })
 }
 
-fn generate_grid() -> (HashSet<Point>, isize) {
-    let mut max_y = 0;
-    let mut grid = HashSet::new();
+// Sand can drift at most one column per row, so the cave only needs to extend as far
+// sideways from the source as it is deep.
+fn generate_grid() -> (Grid<bool, isize>, isize) {
+    let rocks = INPUT.lines().flat_map(generate_points).collect::<Vec<_>>();
+    let max_y = rocks.iter().map(|p| p.y).max().unwrap();
+    let min_x = rocks
+        .iter()
+        .map(|p| p.x)
+        .min()
+        .unwrap()
+        .min(500 - max_y - 2);
+    let max_x = rocks
+        .iter()
+        .map(|p| p.x)
+        .max()
+        .unwrap()
+        .max(500 + max_y + 2);
 
-    for line in INPUT.lines() {
-        for point in generate_points(line) {
-            max_y = max_y.max(point.y);
-            grid.insert(point);
-        }
+    let mut grid = Grid::from_bounds(p2!(min_x, 0), p2!(max_x, max_y + 1), false);
+    for rock in rocks {
+        grid[rock] = true;
     }
 
     (grid, max_y)
@@ -69,9 +78,9 @@ fn part1() -> usize {
                     p2!(sand_loc.x + 1, sand_loc.y + 1),
                 ];
 
-                match candidates.into_iter().find(|p| !grid.contains(p)) {
+                match candidates.into_iter().find(|p| !grid[p]) {
                     None => {
-                        grid.insert(sand_loc);
+                        grid[sand_loc] = true;
                         return false;
                     }
                     Some(new_loc) => {
@@ -95,7 +104,7 @@ fn part2() -> usize {
             let mut sand_loc = p2!(500, 0);
 
             loop {
-                if grid.contains(&p2!(500, 0)) {
+                if grid[p2!(500, 0)] {
                     return true;
                 }
 
@@ -105,12 +114,9 @@ fn part2() -> usize {
                     p2!(sand_loc.x + 1, sand_loc.y + 1),
                 ];
 
-                match candidates
-                    .into_iter()
-                    .find(|p| p.y < max_y + 2 && !grid.contains(p))
-                {
+                match candidates.into_iter().find(|p| p.y < max_y + 2 && !grid[p]) {
                     None => {
-                        grid.insert(sand_loc);
+                        grid[sand_loc] = true;
                         return false;
                     }
                     Some(new_loc) => {
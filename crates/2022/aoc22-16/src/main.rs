@@ -1,226 +1,160 @@
-use std::collections::VecDeque;
-
-use hashbrown::{HashMap, HashSet};
+use hashbrown::HashMap;
+use hymns::graph::DistanceMatrix;
 use hymns::runner::timed_run;
+use hymns::search::branch_and_bound_dominated;
 use regex::Regex;
 
 const INPUT: &str = include_str!("../input.txt");
 
 type Pressure = usize;
 type Valve = &'static str;
-type FlowMap = HashMap<Valve, Pressure>;
-type AdjList = HashMap<Valve, Vec<Valve>>;
 
+// Only the start and the valves worth opening matter; the rest just sit on the paths
+// between them. Node 0 is `AA`.
 struct CaveSystem {
-    flows: FlowMap,
-    adj: AdjList,
+    flows: Vec<Pressure>,
+    distances: DistanceMatrix<usize>,
+}
+
+// Every agent's position and the minutes they have left, sorted by the time left.
+#[derive(Clone, Copy)]
+struct Tour<const N: usize> {
+    agents: [(usize, usize); N],
+    opened: u64,
+    released: Pressure,
+}
+
+impl<const N: usize> Tour<N> {
+    fn new(time: usize) -> Self {
+        Self {
+            agents: [(0, time); N],
+            opened: 0,
+            released: 0,
+        }
+    }
 }
 
 impl CaveSystem {
     fn new() -> Self {
         let re = Regex::new(r"Valve (?P<src>\w+).+=(?P<flow>\d+).+ves? (?P<dest>.+)").unwrap();
 
-        let mut flows = HashMap::default();
-
-        let mut adj = HashMap::default();
+        let mut flows = HashMap::new();
+        let mut adj: HashMap<Valve, Vec<Valve>> = HashMap::new();
 
         for line in INPUT.lines() {
             let caps = re.captures(line).unwrap();
 
             let src = caps.name("src").unwrap().as_str();
-            let rate: usize = caps["flow"].parse().unwrap();
-            let targets: Vec<&str> = caps.name("dest").unwrap().as_str().split(", ").collect();
+            let rate: Pressure = caps["flow"].parse().unwrap();
+            let targets = caps.name("dest").unwrap().as_str().split(", ").collect();
 
             flows.insert(src, rate);
-
             adj.insert(src, targets);
         }
 
-        Self { flows, adj }
+        let mut valves: Vec<Valve> = flows
+            .iter()
+            .filter(|&(_, &flow)| flow > 0)
+            .map(|(&valve, _)| valve)
+            .collect();
+        valves.sort_unstable();
+        valves.insert(0, "AA");
+
+        Self {
+            flows: valves.iter().map(|valve| flows[valve]).collect(),
+            distances: DistanceMatrix::bfs(&valves, |valve| adj[valve].clone()),
+        }
     }
 
-    fn should_open(&self, valve: Valve, state: &State) -> bool {
-        !state.open.contains(valve) && self.flows[valve] > 0
+    // The minutes left after walking from `valve` to `next` and opening it, if there's
+    // time for that.
+    fn time_left(&self, valve: usize, time: usize, next: usize) -> Option<usize> {
+        time.checked_sub(self.distances.get(valve, next)? + 1)
     }
 
-    fn neighbor_moves(&self, state: &State, is_elephant: bool) -> Vec<Action> {
-        let loc = if is_elephant {
-            state.elephant_loc.unwrap()
-        } else {
-            state.my_loc
-        };
-        self.adj[loc]
-            .iter()
-            .map(|neigh| Action::Move(neigh, is_elephant))
-            .collect()
+    fn unopened<const N: usize>(&self, tour: &Tour<N>) -> impl Iterator<Item = usize> + '_ {
+        let opened = tour.opened;
+        (1..self.flows.len()).filter(move |next| opened & (1 << next) == 0)
     }
 
-    fn transition(&self, state: &State, actions: &[Action]) -> State {
-        let mut new_state = state.clone();
-
-        for action in actions {
-            match action {
-                Action::Open(valve) => {
-                    new_state.open.insert(valve);
-                    new_state.rate += self.flows[valve];
-                }
-                Action::Move(valve, is_elephant) => {
-                    if *is_elephant {
-                        new_state.elephant_loc = Some(valve);
-                    } else {
-                        new_state.my_loc = valve;
-                    }
-                }
-            }
-        }
-
-        new_state.ts += 1;
-        new_state.released += new_state.rate;
-
-        new_state
+    // Every way for the agent with the most time left to walk to an unopened valve and
+    // open it before time runs out. Letting it stop instead hands over to the others,
+    // so every plan is reached in just one order.
+    fn moves<'a, const N: usize>(
+        &'a self,
+        tour: &'a Tour<N>,
+    ) -> impl Iterator<Item = Tour<N>> + 'a {
+        let (valve, time) = tour.agents[N - 1];
+
+        let stop = (N > 1 && time > 0).then(|| {
+            let mut agents = tour.agents;
+            agents[N - 1].1 = 0;
+            agents.sort_unstable_by_key(|&(_, time)| time);
+
+            Tour { agents, ..*tour }
+        });
+
+        let moves = self.unopened(tour).filter_map(move |next| {
+            let time = self.time_left(valve, time, next)?;
+
+            let mut agents = tour.agents;
+            agents[N - 1] = (next, time);
+            agents.sort_unstable_by_key(|&(_, time)| time);
+
+            Some(Tour {
+                agents,
+                opened: tour.opened | (1 << next),
+                released: tour.released + time * self.flows[next],
+            })
+        });
+
+        stop.into_iter().chain(moves)
     }
-}
-
-#[derive(Copy, Clone)]
-enum Action {
-    Open(Valve),
-    Move(Valve, bool),
-}
 
-#[derive(Debug, Clone)]
-struct State {
-    ts: usize,
-    my_loc: &'static str,
-    elephant_loc: Option<&'static str>,
-    open: HashSet<&'static str>,
-    released: usize,
-    rate: usize,
-}
+    // As if every unopened valve could be opened by whichever agent gets there first,
+    // going straight from where they are now.
+    fn optimistic_release<const N: usize>(&self, tour: &Tour<N>) -> Pressure {
+        tour.released
+            + self
+                .unopened(tour)
+                .map(|next| {
+                    let time = tour
+                        .agents
+                        .iter()
+                        .filter_map(|&(valve, time)| self.time_left(valve, time, next))
+                        .max()
+                        .unwrap_or(0);
+
+                    time * self.flows[next]
+                })
+                .sum::<Pressure>()
+    }
 
-impl State {
-    fn key(&self) -> (&'static str, Option<&'static str>, usize) {
-        (self.my_loc, self.elephant_loc, self.ts)
+    // The most pressure `N` agents working together can release in `time` minutes.
+    fn max_released<const N: usize>(&self, time: usize) -> Pressure {
+        branch_and_bound_dominated(
+            Tour::<N>::new(time),
+            |tour| self.moves(tour).collect::<Vec<_>>(),
+            |tour| tour.released,
+            |tour| self.optimistic_release(tour),
+            |tour| (tour.agents.map(|(valve, _)| valve), tour.opened),
+            |a, b| {
+                a.released >= b.released && a.agents.iter().zip(&b.agents).all(|(a, b)| a.1 >= b.1)
+            },
+        )
     }
 }
 
 fn part1() -> usize {
     let cave_system = CaveSystem::new();
-    let mut max_released = 0;
-    let mut queue = VecDeque::new();
-
-    queue.push_back(State {
-        ts: 1,
-        my_loc: "AA",
-        elephant_loc: None,
-        open: HashSet::default(),
-        released: 0,
-        rate: 0,
-    });
-
-    let mut memo: HashMap<_, _> = HashMap::default();
-
-    while let Some(cur) = queue.pop_front() {
-        if let Some(&past_score) = memo.get(&cur.key()) {
-            if past_score >= cur.released {
-                continue;
-            }
-        }
-        memo.insert(cur.key(), cur.released);
-
-        if cur.ts == 30 {
-            max_released = max_released.max(cur.released);
-            continue;
-        }
-
-        // open currrent location
-        if cave_system.should_open(cur.my_loc, &cur) {
-            queue.push_back(cave_system.transition(&cur, &[Action::Open(cur.my_loc)]));
-        }
-
-        // don't open
-        queue.extend(
-            cave_system
-                .neighbor_moves(&cur, false)
-                .into_iter()
-                .map(|action| cave_system.transition(&cur, &[action])),
-        );
-    }
 
-    max_released
+    cave_system.max_released::<1>(30)
 }
 
 fn part2() -> usize {
     let cave_system = CaveSystem::new();
 
-    let mut max_released = 0;
-
-    let mut queue = VecDeque::new();
-    queue.push_back(State {
-        ts: 5,
-        my_loc: "AA",
-        elephant_loc: Some("AA"),
-        open: HashSet::default(),
-        released: 0,
-        rate: 0,
-    });
-
-    let mut memo: HashMap<_, _> = HashMap::default();
-
-    while let Some(cur) = queue.pop_front() {
-        if let Some(&past_score) = memo.get(&cur.key()) {
-            if past_score >= cur.released {
-                continue;
-            }
-        }
-        memo.insert(cur.key(), cur.released);
-
-        if cur.ts == 30 {
-            max_released = max_released.max(cur.released);
-            continue;
-        }
-
-        let elephant_loc = cur.elephant_loc.unwrap();
-
-        // i open currrent location
-        if cave_system.should_open(cur.my_loc, &cur) {
-            // elephant opens
-            if elephant_loc != cur.my_loc && cave_system.should_open(elephant_loc, &cur) {
-                queue.push_back(cave_system.transition(
-                    &cur,
-                    &[Action::Open(cur.my_loc), Action::Open(elephant_loc)],
-                ));
-            }
-
-            // elephant moves
-            queue.extend(
-                cave_system
-                    .neighbor_moves(&cur, true)
-                    .into_iter()
-                    .map(|move_action| {
-                        cave_system.transition(&cur, &[Action::Open(cur.my_loc), move_action])
-                    }),
-            );
-        }
-
-        // i move
-        for i_move_action in cave_system.neighbor_moves(&cur, false) {
-            // elephant opens
-            if cave_system.should_open(elephant_loc, &cur) {
-                queue.push_back(
-                    cave_system.transition(&cur, &[i_move_action, Action::Open(elephant_loc)]),
-                );
-            }
-
-            // elephant moves
-            queue.extend(cave_system.neighbor_moves(&cur, true).into_iter().map(
-                |elephant_move_action| {
-                    cave_system.transition(&cur, &[i_move_action, elephant_move_action])
-                },
-            ));
-        }
-    }
-
-    max_released
+    cave_system.max_released::<2>(26)
 }
 
 fn main() {
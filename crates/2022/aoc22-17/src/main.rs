@@ -1,17 +1,18 @@
 use std::collections::hash_map::Entry;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 
+use hymns::bit_grid::BitGrid;
+use hymns::grid::GPoint;
 use hymns::p2;
 use hymns::runner::timed_run;
 use hymns::vector2::Point2;
 
 const INPUT: &str = include_str!("../input.txt");
 
-type Point = Point2<usize>;
-type Rock = Vec<Point>;
+type Rock = &'static [u64];
 
-const MAX_X: usize = 6;
+const WIDTH: usize = 7;
 
 const ROCKS: [RockType; 5] = [
     RockType::Horiz,
@@ -40,19 +41,23 @@ enum RockType {
 #[derive(Debug)]
 struct Chamber {
     height: usize,
-    occupied: HashSet<Point>,
+    occupied: BitGrid,
     rock: Rock,
+    rock_pos: GPoint,
 }
 
 impl Display for Chamber {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for y in (0..=self.height + 7).rev() {
+        let mut rock = BitGrid::new(self.occupied.rows(), WIDTH);
+        rock.stamp(self.rock, self.rock_pos.x, self.rock_pos.y);
+
+        for y in (0..self.occupied.rows()).rev() {
             write!(f, "|")?;
-            for x in 0..=MAX_X {
+            for x in 0..WIDTH {
                 let point = p2!(x, y);
-                if self.occupied.contains(&point) {
+                if self.occupied.get(&point) {
                     write!(f, "#")?;
-                } else if self.rock.iter().any(|&p| p == point) {
+                } else if rock.get(&point) {
                     write!(f, "@")?;
                 } else {
                     write!(f, ".")?;
@@ -70,18 +75,20 @@ impl Chamber {
     fn new() -> Self {
         Self {
             height: 0,
-            occupied: HashSet::new(),
-            rock: vec![],
+            occupied: BitGrid::new(0, WIDTH),
+            rock: &[],
+            rock_pos: GPoint::default(),
         }
     }
 
+    // Rows are listed from the bottom up, and bit `i` is column `i`.
     fn make_rock(rock_type: RockType) -> Rock {
         match rock_type {
-            RockType::Horiz => vec![p2!(0, 0), p2!(1, 0), p2!(2, 0), p2!(3, 0)],
-            RockType::Plus => vec![p2!(1, 2), p2!(0, 1), p2!(1, 1), p2!(2, 1), p2!(1, 0)],
-            RockType::Ell => vec![p2!(2, 2), p2!(2, 1), p2!(0, 0), p2!(1, 0), p2!(2, 0)],
-            RockType::Vert => vec![p2!(0, 3), p2!(0, 2), p2!(0, 1), p2!(0, 0)],
-            RockType::Box => vec![p2!(0, 1), p2!(1, 1), p2!(0, 0), p2!(1, 0)],
+            RockType::Horiz => &[0b1111],
+            RockType::Plus => &[0b010, 0b111, 0b010],
+            RockType::Ell => &[0b111, 0b100, 0b100],
+            RockType::Vert => &[0b1, 0b1, 0b1, 0b1],
+            RockType::Box => &[0b11, 0b11],
         }
     }
 
@@ -90,8 +97,8 @@ impl Chamber {
         let mut min_y = usize::MAX;
 
         for (col, y_val) in result.iter_mut().enumerate() {
-            for y in (0..=self.height).rev() {
-                if self.occupied.contains(&p2!(col, y)) {
+            for y in (0..self.height).rev() {
+                if self.occupied.get(&p2!(col, y)) {
                     *y_val = y;
                     min_y = min_y.min(y);
                     break;
@@ -107,48 +114,37 @@ impl Chamber {
     }
 
     fn move_rock(&mut self, dir: Direction) -> bool {
-        let delta = match dir {
-            Direction::Left => p2!(-1, 0),
-            Direction::Right => p2!(1, 0),
-            Direction::Down => p2!(0, -1),
+        let Point2 { x, y } = self.rock_pos;
+
+        let pos = match dir {
+            Direction::Left => x.checked_sub(1).map(|x| p2!(x, y)),
+            Direction::Right => Some(p2!(x + 1, y)),
+            Direction::Down => y.checked_sub(1).map(|y| p2!(x, y)),
         };
 
-        self.shift_rock(delta)
+        match pos {
+            Some(pos) if !self.occupied.collides(self.rock, pos.x, pos.y) => {
+                self.rock_pos = pos;
+                true
+            }
+            _ => false,
+        }
     }
 
     fn lock_rock(&mut self) {
-        self.occupied.extend(self.rock.iter());
-        self.height = self
-            .height
-            .max(self.rock.iter().map(|p| p.y + 1).max().unwrap());
+        self.occupied
+            .stamp(self.rock, self.rock_pos.x, self.rock_pos.y);
+        self.height = self.height.max(self.rock_pos.y + self.rock.len());
     }
 
     fn create_next_rock(&mut self, rock_type: RockType) {
         self.rock = Self::make_rock(rock_type);
-        self.shift_rock(p2!(2, isize::try_from(self.height).unwrap() + 3));
-    }
-
-    fn shift_rock(&mut self, delta: Point2<isize>) -> bool {
-        let mut new_points = self.rock.clone();
+        self.rock_pos = p2!(2, self.height + 3);
 
-        for p in &mut new_points {
-            match p.x.checked_add_signed(delta.x) {
-                Some(x) if x <= MAX_X => p.x = x,
-                _ => return false,
-            };
-
-            match p.y.checked_add_signed(delta.y) {
-                Some(y) => p.y = y,
-                None => return false,
-            }
-
-            if self.occupied.contains(p) {
-                return false;
-            }
+        let needed_rows = self.rock_pos.y + self.rock.len();
+        if needed_rows > self.occupied.rows() {
+            self.occupied.push_rows(needed_rows - self.occupied.rows());
         }
-
-        self.rock = new_points;
-        true
     }
 }
 
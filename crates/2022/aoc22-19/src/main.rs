@@ -1,10 +1,7 @@
-use hashbrown::HashSet;
-use std::collections::VecDeque;
-use std::iter::IntoIterator;
-
 use regex::Regex;
 
 use hymns::runner::timed_run;
+use hymns::search::branch_and_bound_dominated;
 
 const INPUT: &str = include_str!("../input.txt");
 
@@ -42,6 +39,15 @@ struct Blueprint {
 }
 
 impl Blueprint {
+    fn cost(&self, robot: Robot) -> [u64; 4] {
+        match robot {
+            Robot(Resource::Ore) => [self.ore_robot_ore, 0, 0, 0],
+            Robot(Resource::Clay) => [self.clay_robot_ore, 0, 0, 0],
+            Robot(Resource::Obsidian) => [self.obsidian_robot_ore, self.obsidian_robot_clay, 0, 0],
+            Robot(Resource::Geode) => [self.geode_robot_ore, 0, self.geode_robot_obsidian, 0],
+        }
+    }
+
     fn usable_per_turn(&self, resource: Resource) -> u64 {
         match resource {
             Resource::Ore => [
@@ -62,95 +68,76 @@ impl Blueprint {
 
 #[derive(Debug, Hash, Eq, PartialEq, Clone)]
 struct State {
+    time_left: u64,
     robots: [u64; 4],
     bank: [u64; 4],
 }
 
 impl State {
-    fn new() -> Self {
+    fn new(time_left: u64) -> Self {
         let mut robots = [0; 4];
         robots[Resource::Ore as usize] = 1;
 
         Self {
+            time_left,
             robots,
             bank: [0_u64; 4],
         }
     }
 
-    fn get_robots(&self, robot: Robot) -> u64 {
-        self.robots[robot.0 as usize]
+    fn geodes(&self) -> u64 {
+        self.bank[Resource::Geode as usize]
     }
 
-    fn get_resource(&self, resource: Resource) -> u64 {
-        self.bank[resource as usize]
-    }
+    // Waits until `robot` is affordable and builds it, unless it's not worth having
+    // more of them or there's no time left for it to produce anything.
+    fn build_next(&self, blueprint: &Blueprint, robot: Robot) -> Option<Self> {
+        if self.robots[robot.0 as usize] >= blueprint.usable_per_turn(robot.0) {
+            return None;
+        }
 
-    fn add_resource(&mut self, resource: Resource, count: u64) {
-        self.bank[resource as usize] += count;
-    }
+        let cost = blueprint.cost(robot);
 
-    fn use_resource(&mut self, resource: Resource, count: u64) {
-        self.bank[resource as usize] = self.bank[resource as usize].checked_sub(count).unwrap();
-    }
+        let mut wait = 0;
+        for resource in ALL_RESOURCES {
+            let (have, need) = (self.bank[resource as usize], cost[resource as usize]);
 
-    fn add_robot(&mut self, robot: Robot) {
-        self.robots[robot.0 as usize] += 1;
-    }
+            if have < need {
+                let rate = self.robots[resource as usize];
+                if rate == 0 {
+                    return None;
+                }
 
-    fn increment_resources(&mut self) {
-        for resource in ALL_RESOURCES {
-            // don't increment geodes because we increment them as soon as we build the robot
-            if resource == Resource::Geode {
-                continue;
+                wait = wait.max((need - have).div_ceil(rate));
             }
-            self.bank[resource as usize] += self.get_robots(Robot(resource));
         }
-    }
 
-    fn can_build(&self, blueprint: &Blueprint, robot: Robot) -> bool {
-        if self.get_robots(robot) >= blueprint.usable_per_turn(robot.0) {
-            return false;
+        if wait + 1 >= self.time_left {
+            return None;
         }
 
-        match robot {
-            Robot(Resource::Ore) => self.get_resource(Resource::Ore) >= blueprint.ore_robot_ore,
-            Robot(Resource::Clay) => self.get_resource(Resource::Ore) >= blueprint.clay_robot_ore,
-            Robot(Resource::Obsidian) => {
-                self.get_resource(Resource::Ore) >= blueprint.obsidian_robot_ore
-                    && self.get_resource(Resource::Clay) >= blueprint.obsidian_robot_clay
-            }
-            Robot(Resource::Geode) => {
-                self.get_resource(Resource::Ore) >= blueprint.geode_robot_ore
-                    && self.get_resource(Resource::Obsidian) >= blueprint.geode_robot_obsidian
-            }
+        let mut next = self.clone();
+        next.time_left -= wait + 1;
+
+        for resource in ALL_RESOURCES {
+            let i = resource as usize;
+            next.bank[i] = next.bank[i] + next.robots[i] * (wait + 1) - cost[i];
         }
-    }
 
-    fn build(&mut self, blueprint: &Blueprint, robot: Robot) {
-        match robot {
-            Robot(Resource::Ore) => {
-                self.use_resource(Resource::Ore, blueprint.ore_robot_ore);
-            }
-            Robot(Resource::Clay) => {
-                self.use_resource(Resource::Ore, blueprint.clay_robot_ore);
-            }
-            Robot(Resource::Obsidian) => {
-                self.use_resource(Resource::Ore, blueprint.obsidian_robot_ore);
-                self.use_resource(Resource::Clay, blueprint.obsidian_robot_clay);
-            }
-            Robot(Resource::Geode) => {
-                self.use_resource(Resource::Ore, blueprint.geode_robot_ore);
-                self.use_resource(Resource::Obsidian, blueprint.geode_robot_obsidian);
-            }
+        // geode robots are never counted; instead, everything they'll crack is banked
+        // as soon as they're built
+        if robot.0 == Resource::Geode {
+            next.bank[Resource::Geode as usize] += next.time_left;
+        } else {
+            next.robots[robot.0 as usize] += 1;
         }
 
-        self.add_robot(robot);
+        Some(next)
     }
 
-    fn ticked(&self) -> Self {
-        let mut new_state = self.clone();
-        new_state.increment_resources();
-        new_state
+    // The geodes we'd end with if a geode robot could be built every remaining minute.
+    fn optimistic_geodes(&self) -> u64 {
+        self.geodes() + self.time_left * self.time_left.saturating_sub(1) / 2
     }
 }
 
@@ -179,64 +166,26 @@ fn load_blueprints() -> Vec<Blueprint> {
 }
 
 fn run_simulation(step_count: u64, blueprints: &[Blueprint]) -> Vec<(u64, u64)> {
-    let mut result = vec![];
-
-    for blueprint in blueprints {
-        let mut max_geode_count = 0;
-        let mut seen_states: HashSet<State> = HashSet::default();
-
-        let mut queue = VecDeque::new();
-        queue.push_back(State::new());
-        seen_states.insert(State::new());
-
-        for ts in 0..step_count {
-            let mut next_states = vec![];
-
-            for _ in 0..queue.len() {
-                let cur_state = queue.pop_front().unwrap();
-                if cur_state.get_resource(Resource::Geode) < max_geode_count {
-                    continue;
-                }
-
-                max_geode_count = max_geode_count.max(cur_state.get_resource(Resource::Geode));
-
-                // if we can build a geode robot, build it
-                if cur_state.can_build(blueprint, Robot(Resource::Geode)) {
-                    let mut new_state = cur_state.ticked();
-                    new_state.build(blueprint, Robot(Resource::Geode));
-
-                    // immediately add all geodes this robot will create
-                    let t_rem: u64 = step_count - ts - 1;
-                    new_state.add_resource(Resource::Geode, t_rem);
-
-                    next_states.push(new_state);
-                } else {
-                    // build other robots
-                    for resource in [Resource::Obsidian, Resource::Clay, Resource::Ore] {
-                        if cur_state.can_build(blueprint, Robot(resource)) {
-                            let mut new_state = cur_state.ticked();
-                            new_state.build(blueprint, Robot(resource));
-
-                            next_states.push(new_state);
-                        }
-                    }
-
-                    // build nothing
-                    next_states.push(cur_state.ticked());
-                }
-            }
-
-            queue.extend(
-                next_states
-                    .drain(..)
-                    .filter(|state| seen_states.insert(state.clone())),
+    blueprints
+        .iter()
+        .map(|blueprint| {
+            let max_geode_count = branch_and_bound_dominated(
+                State::new(step_count),
+                |state| {
+                    ALL_RESOURCES
+                        .into_iter()
+                        .filter_map(|resource| state.build_next(blueprint, Robot(resource)))
+                        .collect::<Vec<_>>()
+                },
+                State::geodes,
+                State::optimistic_geodes,
+                |state| (state.time_left, state.robots),
+                |a, b| a.bank.iter().zip(&b.bank).all(|(x, y)| x >= y),
             );
-        }
 
-        result.push((blueprint.id, max_geode_count));
-    }
-
-    result
+            (blueprint.id, max_geode_count)
+        })
+        .collect()
 }
 
 fn part1() -> u64 {
@@ -1,8 +1,9 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{HashMap, HashSet};
 use std::mem::swap;
 
 use hymns::math::lcm;
 use hymns::p2;
+use hymns::pathfinding::bfs;
 use hymns::runner::timed_run;
 use hymns::vector2::Point2;
 
@@ -151,36 +152,27 @@ impl Valley {
     }
 }
 
+// States are positions paired with the blizzard phase, which repeats every
+// `valley.states.len()` minutes.
 fn navigate(valley: &Valley, start_ts: Timestamp) -> usize {
-    let mut queue = VecDeque::new();
-
-    let mut seen_locations = HashSet::new();
-    queue.push_back((valley.start, start_ts));
-
-    for ts in start_ts.. {
-        for _ in 0..queue.len() {
-            let (cur_loc, cur_ts) = queue.pop_front().unwrap();
-
-            if cur_loc == valley.end {
-                return ts - start_ts;
-            }
-
-            if !seen_locations.insert((cur_loc, cur_ts % valley.states.len())) {
-                continue;
-            }
-
-            let next_ts = ts + 1;
-
-            queue.extend(
-                valley
-                    .moves_at_timestamp(next_ts, cur_loc)
-                    .into_iter()
-                    .map(|p| (p, next_ts)),
-            );
-        }
-    }
-
-    unreachable!()
+    let period = valley.states.len();
+
+    let (_, duration) = bfs(
+        &[(valley.start, start_ts % period)],
+        |&(loc, _)| loc == valley.end,
+        |&(loc, ts)| {
+            let next_ts = (ts + 1) % period;
+
+            valley
+                .moves_at_timestamp(next_ts, loc)
+                .into_iter()
+                .map(|p| (p, next_ts))
+                .collect()
+        },
+    )
+    .unwrap();
+
+    duration
 }
 
 fn part1() -> usize {
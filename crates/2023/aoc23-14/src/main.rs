@@ -1,22 +1,24 @@
 use std::collections::HashMap;
-use std::fmt::{Display, Formatter};
 
-use hymns::grid::{GPoint, Grid};
+use hymns::grid::{GPoint, Grid, GridCell};
 use hymns::p2;
 use hymns::runner::timed_run;
 use hymns::vector2::Point2;
 
 use crate::Direction::{East, North, South, West};
-use crate::Surface::{Cube, Empty, Rounded};
+use crate::Surface::{Empty, Rounded};
 
 const INPUT: &str = include_str!("../input.txt");
 
 type Dish = Grid<Surface>;
 
-#[derive(Eq, PartialEq, Hash, Copy, Clone)]
+#[derive(GridCell, Eq, PartialEq, Hash, Copy, Clone)]
 enum Surface {
+    #[cell('O')]
     Rounded,
+    #[cell('#')]
     Cube,
+    #[cell('.')]
     Empty,
 }
 
@@ -28,28 +30,6 @@ enum Direction {
     West,
 }
 
-impl Display for Surface {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let c = match self {
-            Rounded => 'O',
-            Cube => '#',
-            Empty => '.',
-        };
-        write!(f, "{c}")
-    }
-}
-
-impl From<char> for Surface {
-    fn from(value: char) -> Self {
-        match value {
-            'O' => Rounded,
-            '#' => Cube,
-            '.' => Empty,
-            _ => unreachable!(),
-        }
-    }
-}
-
 fn move_point<'a>(
     direction: Direction,
     grid: &'a Dish,
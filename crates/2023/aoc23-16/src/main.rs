@@ -2,22 +2,27 @@ use std::collections::HashSet;
 
 use itertools::Itertools;
 
-use hymns::grid::{GPoint, Grid};
+use hymns::grid::{GPoint, Grid, GridCell};
 use hymns::p2;
 use hymns::runner::timed_run;
 use hymns::vector2::Direction::{Down, Left, Right, Up};
 use hymns::vector2::{Direction, Point2};
 
-use crate::Tile::{Backward, Empty, Forward, Horizontal, Vertical};
+use crate::Tile::{Backward, Forward, Horizontal, Vertical};
 
 const INPUT: &str = include_str!("../input.txt");
 
-#[derive(Debug, Copy, Clone)]
+#[derive(GridCell, Debug, Copy, Clone)]
 enum Tile {
+    #[cell('.')]
     Empty,
+    #[cell('|')]
     Vertical,
+    #[cell('-')]
     Horizontal,
+    #[cell('/')]
     Forward,
+    #[cell('\\')]
     Backward,
 }
 
@@ -87,19 +92,6 @@ fn count_energized(grid: &Grid<Tile>, start_point: GPoint, start_direction: Dire
     seen.into_iter().unique_by(|b| b.location).count()
 }
 
-impl From<char> for Tile {
-    fn from(value: char) -> Self {
-        match value {
-            '.' => Empty,
-            '/' => Forward,
-            '\\' => Backward,
-            '-' => Horizontal,
-            '|' => Vertical,
-            _ => unreachable!(),
-        }
-    }
-}
-
 fn part1() -> usize {
     let grid: Grid<Tile> = INPUT.parse().unwrap();
     count_energized(&grid, p2!(0, 0), Right)
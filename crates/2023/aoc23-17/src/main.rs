@@ -1,25 +1,23 @@
-use std::hash::Hash;
-
 use itertools::Itertools;
 
 use hymns::grid::{GPoint, Grid};
 use hymns::p2;
-use hymns::pathfinding::a_star;
+use hymns::pathfinding::a_star_buckets;
 use hymns::runner::timed_run;
 use hymns::vector2::{Direction, Point2, FOUR_NEIGHBORS};
 
 const INPUT: &str = include_str!("../input.txt");
 
+// Each state is where a straight run ended and the direction it ran in, so the
+// next run has to turn.
 const STARTS: [State; 2] = [
     State {
         point: p2!(0, 0),
         dir: Direction::Right,
-        consecutive: 0,
     },
     State {
         point: p2!(0, 0),
         dir: Direction::Down,
-        consecutive: 0,
     },
 ];
 
@@ -27,41 +25,39 @@ const STARTS: [State; 2] = [
 struct State {
     point: GPoint,
     dir: Direction,
-    consecutive: usize,
 }
 
 impl State {
-    fn get_neighbors(
+    fn get_moves(
         &self,
         grid: &Grid<u8>,
         min_consecutive: usize,
         max_consecutive: usize,
-    ) -> Vec<Self> {
-        let dirs = if self.consecutive < min_consecutive {
-            vec![self.dir]
-        } else {
-            FOUR_NEIGHBORS
-                .into_iter()
-                .filter(|&dir| {
-                    dir != self.dir.inverted()
-                        && !(self.consecutive == max_consecutive && self.dir == dir)
-                })
-                .collect_vec()
-        };
-
-        dirs.into_iter()
-            .filter_map(|dir| {
-                grid.get_neighbor(&self.point, dir).map(|neigh| Self {
-                    point: neigh,
-                    dir,
-                    consecutive: if dir == self.dir {
-                        self.consecutive + 1
-                    } else {
-                        1
-                    },
-                })
-            })
-            .collect_vec()
+    ) -> Vec<(Self, usize)> {
+        let mut moves = vec![];
+
+        for dir in FOUR_NEIGHBORS
+            .into_iter()
+            .filter(|&dir| dir != self.dir && dir != self.dir.inverted())
+        {
+            let mut point = self.point;
+            let mut heat = 0;
+
+            for steps in 1..=max_consecutive {
+                let Some(next) = grid.get_neighbor(&point, dir) else {
+                    break;
+                };
+
+                point = next;
+                heat += usize::from(grid[point]);
+
+                if steps >= min_consecutive {
+                    moves.push((Self { point, dir }, heat));
+                }
+            }
+        }
+
+        moves
     }
 }
 
@@ -79,11 +75,10 @@ fn solve<const MIN_CONSECUTIVE: usize, const MAX_CONSECUTIVE: usize>() -> usize
 
     let goal = p2!(grid.cols() - 1, grid.rows() - 1);
 
-    let (_, min_heat) = a_star(
+    let (_, min_heat) = a_star_buckets(
         &STARTS,
-        |p| p.point == goal && p.consecutive >= MIN_CONSECUTIVE,
-        |_, q| usize::from(grid[q.point]),
-        |state| state.get_neighbors(&grid, MIN_CONSECUTIVE, MAX_CONSECUTIVE),
+        |p| p.point == goal,
+        |state| state.get_moves(&grid, MIN_CONSECUTIVE, MAX_CONSECUTIVE),
         |p| p.point.manhattan_dist(&goal),
     )
     .unwrap();
@@ -92,7 +87,7 @@ fn solve<const MIN_CONSECUTIVE: usize, const MAX_CONSECUTIVE: usize>() -> usize
 }
 
 fn part1() -> usize {
-    solve::<0, 3>()
+    solve::<1, 3>()
 }
 
 fn part2() -> usize {
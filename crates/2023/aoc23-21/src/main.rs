@@ -1,42 +1,21 @@
-use crate::Plot::{Empty, Rock, Start};
-use hymns::grid::Grid;
+use crate::Plot::{Empty, Start};
+use hymns::grid::{Grid, GridCell};
 use hymns::runner::timed_run;
 use std::collections::HashSet;
-use std::fmt::{Display, Formatter};
 use std::mem;
 
 const INPUT: &str = include_str!("../input.txt");
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(GridCell, Debug, Eq, PartialEq)]
 enum Plot {
+    #[cell('.')]
     Empty,
+    #[cell('#')]
     Rock,
+    #[cell('S')]
     Start,
 }
 
-impl From<char> for Plot {
-    fn from(value: char) -> Self {
-        match value {
-            '.' => Empty,
-            '#' => Rock,
-            'S' => Start,
-            _ => unreachable!(),
-        }
-    }
-}
-
-impl Display for Plot {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let c = match self {
-            Empty => '.',
-            Rock => '#',
-            Start => 'S',
-        };
-
-        write!(f, "{c}")
-    }
-}
-
 fn part1() -> usize {
     let mut grid: Grid<Plot> = INPUT.parse().unwrap();
 
@@ -0,0 +1,110 @@
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Ident, LitChar};
+
+struct Cell {
+    variant: Ident,
+    c: LitChar,
+}
+
+fn parse_cells(input: &DeriveInput) -> syn::Result<Vec<Cell>> {
+    let Data::Enum(data) = &input.data else {
+        return Err(Error::new(
+            Span::call_site(),
+            "GridCell can only be derived for enums",
+        ));
+    };
+
+    let mut cells: Vec<Cell> = vec![];
+
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new_spanned(
+                variant,
+                "GridCell variants can't have fields",
+            ));
+        }
+
+        let mut attrs = variant.attrs.iter().filter(|a| a.path().is_ident("cell"));
+
+        let Some(attr) = attrs.next() else {
+            return Err(Error::new_spanned(
+                variant,
+                "missing #[cell('c')] attribute",
+            ));
+        };
+
+        if let Some(extra) = attrs.next() {
+            return Err(Error::new_spanned(extra, "duplicate #[cell] attribute"));
+        }
+
+        let c: LitChar = attr.parse_args()?;
+
+        if let Some(other) = cells.iter().find(|cell| cell.c.value() == c.value()) {
+            return Err(Error::new_spanned(
+                &c,
+                format!("{:?} is already used by {}", c.value(), other.variant),
+            ));
+        }
+
+        cells.push(Cell {
+            variant: variant.ident.clone(),
+            c,
+        });
+    }
+
+    Ok(cells)
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let cells = parse_cells(input)?;
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let variants = cells.iter().map(|cell| &cell.variant).collect::<Vec<_>>();
+    let chars = cells.iter().map(|cell| &cell.c).collect::<Vec<_>>();
+
+    Ok(quote! {
+        impl #impl_generics ::core::convert::TryFrom<char> for #name #ty_generics #where_clause {
+            type Error = char;
+
+            fn try_from(c: char) -> ::core::result::Result<Self, Self::Error> {
+                match c {
+                    #(#chars => ::core::result::Result::Ok(Self::#variants),)*
+                    _ => ::core::result::Result::Err(c),
+                }
+            }
+        }
+
+        impl #impl_generics ::core::convert::From<&#name #ty_generics> for char #where_clause {
+            fn from(cell: &#name #ty_generics) -> Self {
+                match cell {
+                    #(#name::#variants => #chars,)*
+                }
+            }
+        }
+
+        impl #impl_generics ::core::convert::From<#name #ty_generics> for char #where_clause {
+            fn from(cell: #name #ty_generics) -> Self {
+                char::from(&cell)
+            }
+        }
+
+        impl #impl_generics ::core::fmt::Display for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::Write::write_char(f, char::from(self))
+            }
+        }
+    })
+}
+
+#[proc_macro_derive(GridCell, attributes(cell))]
+pub fn derive_grid_cell(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,250 @@
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+use crate::grid::{GPoint, Grid};
+use crate::png;
+use crate::render::Color;
+
+#[derive(Clone, Default)]
+pub struct Recorder<T> {
+    frames: Vec<Grid<T>>,
+}
+
+impl<T> Recorder<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self { frames: vec![] }
+    }
+
+    pub fn push(&mut self, frame: Grid<T>) {
+        self.frames.push(frame);
+    }
+
+    #[must_use]
+    pub fn frames(&self) -> &[Grid<T>] {
+        &self.frames
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    // Encodes the frames as an animated PNG, drawing each cell as a `scale` by `scale`
+    // square, or returns `None` if nothing was recorded. All frames must have the same
+    // dimensions, and an `fps` of 0 plays at 1 frame per second.
+    #[must_use]
+    pub fn encode_apng<F>(&self, palette: F, scale: usize, fps: u16) -> Option<Vec<u8>>
+    where
+        F: Fn(&T) -> Color,
+    {
+        assert!(scale > 0);
+
+        let first = self.frames.first()?;
+        let (rows, cols) = (first.rows(), first.cols());
+        let fps = fps.max(1);
+        assert!(
+            self.frames
+                .iter()
+                .all(|f| f.rows() == rows && f.cols() == cols),
+            "all frames must have the same dimensions"
+        );
+
+        let width = u32::try_from(cols * scale).unwrap();
+        let height = u32::try_from(rows * scale).unwrap();
+
+        let mut out = png::SIGNATURE.to_vec();
+        png::write_chunk(&mut out, *b"IHDR", &png::ihdr(cols * scale, rows * scale));
+
+        let mut actl = vec![];
+        actl.extend(u32::try_from(self.frames.len()).unwrap().to_be_bytes());
+        actl.extend(0_u32.to_be_bytes());
+        png::write_chunk(&mut out, *b"acTL", &actl);
+
+        let mut sequence = 0_u32;
+
+        for (i, frame) in self.frames.iter().enumerate() {
+            let mut fctl = vec![];
+            fctl.extend(sequence.to_be_bytes());
+            fctl.extend(width.to_be_bytes());
+            fctl.extend(height.to_be_bytes());
+            fctl.extend(0_u32.to_be_bytes());
+            fctl.extend(0_u32.to_be_bytes());
+            fctl.extend(1_u16.to_be_bytes());
+            fctl.extend(fps.to_be_bytes());
+            fctl.extend([0, 0]);
+            png::write_chunk(&mut out, *b"fcTL", &fctl);
+            sequence += 1;
+
+            let scanlines = png::rgb_scanlines(cols * scale, rows * scale, |x, y| {
+                palette(&frame[GPoint::new(x / scale, y / scale)])
+            });
+            let data = png::zlib_stored(&scanlines);
+
+            if i == 0 {
+                png::write_chunk(&mut out, *b"IDAT", &data);
+            } else {
+                let mut fdat = sequence.to_be_bytes().to_vec();
+                fdat.extend(data);
+                png::write_chunk(&mut out, *b"fdAT", &fdat);
+                sequence += 1;
+            }
+        }
+
+        png::write_chunk(&mut out, *b"IEND", &[]);
+
+        Some(out)
+    }
+
+    /// Writes the animation to `path` as an APNG.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidInput` error if nothing was recorded, or any error raised while
+    /// writing the file.
+    pub fn write_apng<F, P>(&self, path: P, palette: F, scale: usize, fps: u16) -> io::Result<()>
+    where
+        F: Fn(&T) -> Color,
+        P: AsRef<Path>,
+    {
+        let bytes = self.encode_apng(palette, scale, fps).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "no frames were recorded")
+        })?;
+
+        fs::write(path, bytes)
+    }
+}
+
+impl<T> Recorder<T>
+where
+    T: Clone,
+{
+    pub fn record(&mut self, frame: &Grid<T>) {
+        self.frames.push(frame.clone());
+    }
+}
+
+impl<T> Recorder<T>
+where
+    T: Display,
+{
+    // Redraws each frame in place in the terminal. Like `encode_apng`, an `fps` of 0
+    // plays at 1 frame per second.
+    pub fn replay(&self, fps: u16) {
+        let delay = Duration::from_secs(1) / u32::from(fps.max(1));
+        let mut stdout = io::stdout().lock();
+
+        for (i, frame) in self.frames.iter().enumerate() {
+            let _ = writeln!(stdout, "\x1b[H\x1b[2J{frame}frame {}/{}", i + 1, self.len());
+            let _ = stdout.flush();
+
+            thread::sleep(delay);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record() {
+        let mut grid = Grid::new(vec![vec![false, false]]);
+        let mut recorder = Recorder::new();
+        assert!(recorder.is_empty());
+
+        recorder.record(&grid);
+        grid[GPoint::new(1, 0)] = true;
+        recorder.record(&grid);
+        recorder.push(Grid::new(vec![vec![true, true]]));
+
+        assert_eq!(recorder.len(), 3);
+        assert_eq!(recorder.frames()[0], Grid::new(vec![vec![false, false]]));
+        assert_eq!(recorder.frames()[1], grid);
+    }
+
+    #[test]
+    fn test_encode_apng() {
+        let mut recorder = Recorder::new();
+        recorder.push(Grid::new(vec![vec![false, true]]));
+        recorder.push(Grid::new(vec![vec![true, false]]));
+
+        let palette = |&on: &bool| if on { Color(255, 0, 0) } else { Color(0, 0, 0) };
+        let bytes = recorder.encode_apng(palette, 2, 10).unwrap();
+
+        let chunks = png::read_chunks(&bytes);
+        let kinds = chunks
+            .iter()
+            .map(|(kind, _)| std::str::from_utf8(kind).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            ["IHDR", "acTL", "fcTL", "IDAT", "fcTL", "fdAT", "IEND"]
+        );
+
+        assert_eq!(chunks[0].1, png::ihdr(4, 2));
+        assert_eq!(chunks[1].1, [0, 0, 0, 2, 0, 0, 0, 0]);
+
+        let fctl = &chunks[4].1;
+        assert_eq!(fctl[..4], 1_u32.to_be_bytes());
+        assert_eq!(fctl[4..12], [0, 0, 0, 4, 0, 0, 0, 2]);
+        assert_eq!(fctl[20..24], [0, 1, 0, 10]);
+
+        let first = png::unzlib_stored(&chunks[3].1);
+        assert_eq!(
+            first,
+            [
+                [0, 0, 0, 0, 0, 0, 0, 255, 0, 0, 255, 0, 0],
+                [0, 0, 0, 0, 0, 0, 0, 255, 0, 0, 255, 0, 0]
+            ]
+            .concat()
+        );
+
+        assert_eq!(chunks[5].1[..4], 2_u32.to_be_bytes());
+        let second = png::unzlib_stored(&chunks[5].1[4..]);
+        assert_eq!(second[..7], [0, 255, 0, 0, 255, 0, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "same dimensions")]
+    fn test_encode_mismatched_frames() {
+        let mut recorder = Recorder::new();
+        recorder.push(Grid::new(vec![vec![false]]));
+        recorder.push(Grid::new(vec![vec![false, true]]));
+
+        let _ = recorder.encode_apng(|_| Color(0, 0, 0), 1, 1);
+    }
+
+    #[test]
+    fn test_encode_empty() {
+        let recorder = Recorder::<bool>::new();
+        assert_eq!(recorder.encode_apng(|_| Color(0, 0, 0), 1, 10), None);
+
+        let err = recorder
+            .write_apng("unused.png", |_| Color(0, 0, 0), 1, 10)
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_zero_fps() {
+        let mut recorder = Recorder::new();
+        recorder.push(Grid::new(vec![vec![false]]));
+
+        let palette = |_: &bool| Color(0, 0, 0);
+        assert_eq!(
+            recorder.encode_apng(palette, 1, 0),
+            recorder.encode_apng(palette, 1, 1)
+        );
+    }
+}
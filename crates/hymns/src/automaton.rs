@@ -0,0 +1,366 @@
+use std::hash::Hash;
+use std::mem;
+
+use hashbrown::{HashMap, HashSet};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::grid::{GPoint, Grid};
+use crate::vector2::Direction;
+
+pub struct CellView<'a, T> {
+    pub point: GPoint,
+    pub value: &'a T,
+    grid: &'a Grid<T>,
+    neighborhood: &'a [Direction],
+}
+
+impl<'a, T> CellView<'a, T> {
+    #[must_use]
+    pub fn grid(&self) -> &'a Grid<T> {
+        self.grid
+    }
+
+    pub fn neighbors(&self) -> impl Iterator<Item = &'a T> + '_ {
+        self.grid
+            .get_neighbors(&self.point, self.neighborhood)
+            .into_iter()
+            .map(|p| &self.grid[p])
+    }
+
+    pub fn count_neighbors<F>(&self, predicate: F) -> usize
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.neighbors().filter(|val| predicate(val)).count()
+    }
+}
+
+pub struct GridAutomaton<T, R> {
+    current: Grid<T>,
+    next: Grid<T>,
+    neighborhood: Vec<Direction>,
+    rule: R,
+    steps: usize,
+}
+
+impl<T, R> GridAutomaton<T, R>
+where
+    T: Clone + PartialEq,
+    R: Fn(&CellView<'_, T>) -> T,
+{
+    pub fn new(grid: Grid<T>, neighborhood: &[Direction], rule: R) -> Self {
+        Self {
+            next: grid.clone(),
+            current: grid,
+            neighborhood: neighborhood.to_vec(),
+            rule,
+            steps: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn grid(&self) -> &Grid<T> {
+        &self.current
+    }
+
+    #[must_use]
+    pub fn into_grid(self) -> Grid<T> {
+        self.current
+    }
+
+    #[must_use]
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    fn compute_row(&self, y: usize, row: &mut [T]) -> bool {
+        let mut changed = false;
+
+        for (x, cell) in row.iter_mut().enumerate() {
+            let point = GPoint::new(x, y);
+
+            let view = CellView {
+                point,
+                value: &self.current[point],
+                grid: &self.current,
+                neighborhood: &self.neighborhood,
+            };
+
+            let new = (self.rule)(&view);
+            changed |= new != *view.value;
+            *cell = new;
+        }
+
+        changed
+    }
+
+    // Returns whether any cell changed.
+    pub fn step(&mut self) -> bool {
+        let mut next = mem::replace(&mut self.next, Grid::new(vec![]));
+
+        let mut changed = false;
+        for (y, row) in next.iter_rows_mut().enumerate() {
+            changed |= self.compute_row(y, row);
+        }
+
+        self.next = mem::replace(&mut self.current, next);
+        self.steps += 1;
+
+        changed
+    }
+
+    pub fn run(&mut self, steps: usize) {
+        for _ in 0..steps {
+            self.step();
+        }
+    }
+
+    // Steps until the grid stops changing, returning the number of steps taken.
+    pub fn run_until_stable(&mut self) -> usize {
+        let start = self.steps;
+        while self.step() {}
+        self.steps - start
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<T, R> GridAutomaton<T, R>
+where
+    T: Clone + PartialEq + Send + Sync,
+    R: Fn(&CellView<'_, T>) -> T + Sync,
+{
+    pub fn par_step(&mut self) -> bool {
+        let mut next = mem::replace(&mut self.next, Grid::new(vec![]));
+
+        let changed = next
+            .iter_rows_mut()
+            .enumerate()
+            .collect::<Vec<_>>()
+            .into_par_iter()
+            .map(|(y, row)| self.compute_row(y, row))
+            .reduce(|| false, |a, b| a || b);
+
+        self.next = mem::replace(&mut self.current, next);
+        self.steps += 1;
+
+        changed
+    }
+
+    pub fn par_run(&mut self, steps: usize) {
+        for _ in 0..steps {
+            self.par_step();
+        }
+    }
+
+    pub fn par_run_until_stable(&mut self) -> usize {
+        let start = self.steps;
+        while self.par_step() {}
+        self.steps - start
+    }
+}
+
+pub struct SparseAutomaton<P, N, R> {
+    cells: HashSet<P>,
+    neighbors: N,
+    rule: R,
+    steps: usize,
+}
+
+impl<P, N, I, R> SparseAutomaton<P, N, R>
+where
+    P: Hash + Eq + Clone,
+    N: Fn(&P) -> I,
+    I: IntoIterator<Item = P>,
+    R: Fn(bool, usize) -> bool,
+{
+    // `rule` receives whether a cell is alive and its number of live neighbors,
+    // and returns whether it's alive in the next step.
+    pub fn new(cells: impl IntoIterator<Item = P>, neighbors: N, rule: R) -> Self {
+        Self {
+            cells: cells.into_iter().collect(),
+            neighbors,
+            rule,
+            steps: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn cells(&self) -> &HashSet<P> {
+        &self.cells
+    }
+
+    #[must_use]
+    pub fn into_cells(self) -> HashSet<P> {
+        self.cells
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    #[must_use]
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    pub fn step(&mut self) -> bool {
+        let mut counts: HashMap<P, usize> = HashMap::with_capacity(self.cells.len());
+
+        for cell in &self.cells {
+            counts.entry(cell.clone()).or_insert(0);
+
+            for neighbor in (self.neighbors)(cell) {
+                *counts.entry(neighbor).or_insert(0) += 1;
+            }
+        }
+
+        let next: HashSet<P> = counts
+            .into_iter()
+            .filter(|(cell, count)| (self.rule)(self.cells.contains(cell), *count))
+            .map(|(cell, _)| cell)
+            .collect();
+
+        let changed = next != self.cells;
+
+        self.cells = next;
+        self.steps += 1;
+
+        changed
+    }
+
+    pub fn run(&mut self, steps: usize) {
+        for _ in 0..steps {
+            self.step();
+        }
+    }
+
+    pub fn run_until_stable(&mut self) -> usize {
+        let start = self.steps;
+        while self.step() {}
+        self.steps - start
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+    use crate::p2;
+    use crate::vector2::{Point2, EIGHT_NEIGHBORS, FOUR_NEIGHBORS};
+
+    fn life(view: &CellView<'_, bool>) -> bool {
+        matches!(
+            (view.value, view.count_neighbors(|&alive| alive)),
+            (true, 2 | 3) | (false, 3)
+        )
+    }
+
+    fn parse(s: &str) -> Grid<bool> {
+        Grid::new(
+            s.lines()
+                .map(|line| line.chars().map(|c| c == '#').collect())
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_grid_blinker() {
+        let horizontal = parse(".....\n.....\n.###.\n.....\n.....\n");
+        let vertical = parse(".....\n..#..\n..#..\n..#..\n.....\n");
+
+        let mut automaton = GridAutomaton::new(horizontal.clone(), &EIGHT_NEIGHBORS, life);
+
+        assert!(automaton.step());
+        assert_eq!(automaton.grid(), &vertical);
+        assert!(automaton.step());
+        assert_eq!(automaton.grid(), &horizontal);
+
+        automaton.run(3);
+        assert_eq!(automaton.steps(), 5);
+        assert_eq!(automaton.into_grid(), vertical);
+    }
+
+    #[test]
+    fn test_grid_stable() {
+        let grid = Grid::new(vec![vec![0, 0, 0, 9], vec![0, 0, 0, 0]]);
+
+        // spread the maximum of each cell's orthogonal neighborhood
+        let mut automaton = GridAutomaton::new(grid, &FOUR_NEIGHBORS, |view| {
+            view.neighbors().chain([view.value]).copied().max().unwrap()
+        });
+
+        assert_eq!(automaton.run_until_stable(), 5);
+        assert_eq!(automaton.steps(), 5);
+        assert!(automaton.grid().iter_values().all(|&v| v == 9));
+
+        assert_eq!(automaton.run_until_stable(), 1);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_grid_parallel() {
+        let grid = parse("......\n.##...\n.##...\n...##.\n...##.\n......\n");
+
+        let mut sequential = GridAutomaton::new(grid.clone(), &EIGHT_NEIGHBORS, life);
+        let mut parallel = GridAutomaton::new(grid, &EIGHT_NEIGHBORS, life);
+
+        for _ in 0..4 {
+            assert_eq!(sequential.step(), parallel.par_step());
+            assert_eq!(sequential.grid(), parallel.grid());
+        }
+    }
+
+    #[test]
+    fn test_sparse_glider() {
+        let glider = [p2!(1, 0), p2!(2, 1), p2!(0, 2), p2!(1, 2), p2!(2, 2)];
+
+        let mut automaton = SparseAutomaton::new(
+            glider,
+            |p: &Point2<i32>| p.all_neighbors(true, false),
+            |alive, count| matches!((alive, count), (true, 2 | 3) | (false, 3)),
+        );
+
+        automaton.run(4);
+        assert_eq!(automaton.steps(), 4);
+        assert_eq!(
+            automaton
+                .cells()
+                .iter()
+                .copied()
+                .sorted_by_key(|p| (p.y, p.x))
+                .collect_vec(),
+            glider.map(|p| p + p2!(1, 1)).to_vec()
+        );
+    }
+
+    #[test]
+    fn test_sparse_stable() {
+        let block = [p2!(0, 0), p2!(1, 0), p2!(0, 1), p2!(1, 1)];
+
+        let mut automaton = SparseAutomaton::new(
+            block,
+            |p: &Point2<i32>| p.all_neighbors(true, false),
+            |alive, count| matches!((alive, count), (true, 2 | 3) | (false, 3)),
+        );
+
+        assert_eq!(automaton.run_until_stable(), 1);
+        assert_eq!(automaton.len(), 4);
+
+        let mut automaton = SparseAutomaton::new(
+            [p2!(0, 0)],
+            |p: &Point2<i32>| p.all_neighbors(true, false),
+            |alive, count| alive && count > 0,
+        );
+
+        assert_eq!(automaton.run_until_stable(), 2);
+        assert!(automaton.is_empty());
+    }
+}
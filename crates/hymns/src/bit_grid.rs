@@ -0,0 +1,508 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::{BitAndAssign, BitOrAssign, BitXorAssign};
+
+use crate::grid::{GPoint, Grid};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+#[derive(Eq, PartialEq, Hash, Clone)]
+pub struct BitGrid {
+    words: Vec<u64>,
+    rows: usize,
+    cols: usize,
+    words_per_row: usize,
+}
+
+impl BitGrid {
+    #[must_use]
+    pub fn new(rows: usize, cols: usize) -> Self {
+        let words_per_row = cols.div_ceil(WORD_BITS);
+
+        Self {
+            words: vec![0; rows * words_per_row],
+            rows,
+            cols,
+            words_per_row,
+        }
+    }
+
+    #[must_use]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    #[must_use]
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    #[must_use]
+    pub fn contains(&self, p: GPoint) -> bool {
+        p.y < self.rows && p.x < self.cols
+    }
+
+    fn index(&self, p: &GPoint) -> (usize, u64) {
+        assert!(self.contains(*p), "{p} is outside of the grid");
+
+        (
+            p.y * self.words_per_row + p.x / WORD_BITS,
+            1 << (p.x % WORD_BITS),
+        )
+    }
+
+    // Mask of the valid bits in the last word of each row.
+    fn last_word_mask(&self) -> u64 {
+        match self.cols % WORD_BITS {
+            0 => u64::MAX,
+            n => (1 << n) - 1,
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self, p: &GPoint) -> bool {
+        let (i, bit) = self.index(p);
+        self.words[i] & bit != 0
+    }
+
+    pub fn set(&mut self, p: &GPoint, val: bool) {
+        let (i, bit) = self.index(p);
+
+        if val {
+            self.words[i] |= bit;
+        } else {
+            self.words[i] &= !bit;
+        }
+    }
+
+    pub fn toggle(&mut self, p: &GPoint) {
+        let (i, bit) = self.index(p);
+        self.words[i] ^= bit;
+    }
+
+    #[must_use]
+    pub fn row(&self, row: usize) -> &[u64] {
+        &self.words[row * self.words_per_row..(row + 1) * self.words_per_row]
+    }
+
+    fn row_mut(&mut self, row: usize) -> &mut [u64] {
+        &mut self.words[row * self.words_per_row..(row + 1) * self.words_per_row]
+    }
+
+    pub fn push_rows(&mut self, count: usize) {
+        self.rows += count;
+        self.words.resize(self.rows * self.words_per_row, 0);
+    }
+
+    pub fn truncate_rows(&mut self, rows: usize) {
+        self.rows = self.rows.min(rows);
+        self.words.truncate(self.rows * self.words_per_row);
+    }
+
+    #[must_use]
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    #[must_use]
+    pub fn row_count_ones(&self, row: usize) -> usize {
+        self.row(row).iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn iter_ones(&self) -> impl Iterator<Item = GPoint> + '_ {
+        self.words.iter().enumerate().flat_map(move |(i, &word)| {
+            let y = i / self.words_per_row;
+            let x_base = (i % self.words_per_row) * WORD_BITS;
+
+            let mut remaining = word;
+
+            std::iter::from_fn(move || {
+                if remaining == 0 {
+                    return None;
+                }
+
+                let bit = remaining.trailing_zeros() as usize;
+                remaining &= remaining - 1;
+
+                Some(GPoint::new(x_base + bit, y))
+            })
+        })
+    }
+
+    fn row_op(&mut self, row: usize, mask: &[u64], op: impl Fn(&mut u64, u64)) {
+        assert_eq!(mask.len(), self.words_per_row);
+
+        let last_word_mask = self.last_word_mask();
+        let words = self.row_mut(row);
+
+        for (word, &m) in words.iter_mut().zip(mask) {
+            op(word, m);
+        }
+
+        if let Some(last) = words.last_mut() {
+            *last &= last_word_mask;
+        }
+    }
+
+    pub fn and_row(&mut self, row: usize, mask: &[u64]) {
+        self.row_op(row, mask, |w, m| *w &= m);
+    }
+
+    pub fn or_row(&mut self, row: usize, mask: &[u64]) {
+        self.row_op(row, mask, |w, m| *w |= m);
+    }
+
+    pub fn xor_row(&mut self, row: usize, mask: &[u64]) {
+        self.row_op(row, mask, |w, m| *w ^= m);
+    }
+
+    // Moves every cell `n` columns to the right (or left, if negative),
+    // dropping cells that fall off the edge.
+    pub fn shift_cols(&mut self, n: isize) {
+        let last_word_mask = self.last_word_mask();
+        let words_per_row = self.words_per_row;
+        let word_shift = n.unsigned_abs() / WORD_BITS;
+        let bit_shift = n.unsigned_abs() % WORD_BITS;
+
+        for row in 0..self.rows {
+            let words = self.row_mut(row);
+            let old = words.to_vec();
+
+            let get = |i: Option<usize>| i.and_then(|i| old.get(i)).copied().unwrap_or(0);
+
+            for (i, word) in words.iter_mut().enumerate() {
+                *word = if n >= 0 {
+                    let src = i.checked_sub(word_shift);
+                    let carry = if bit_shift == 0 {
+                        0
+                    } else {
+                        get(src.and_then(|s| s.checked_sub(1))) >> (WORD_BITS - bit_shift)
+                    };
+                    (get(src) << bit_shift) | carry
+                } else {
+                    let src = Some(i + word_shift);
+                    let carry = if bit_shift == 0 {
+                        0
+                    } else {
+                        get(src.map(|s| s + 1)) << (WORD_BITS - bit_shift)
+                    };
+                    (get(src) >> bit_shift) | carry
+                };
+            }
+
+            if words_per_row > 0 {
+                words[words_per_row - 1] &= last_word_mask;
+            }
+        }
+    }
+
+    // Moves every cell `n` rows down (or up, if negative), dropping cells that fall
+    // off the edge.
+    pub fn shift_rows(&mut self, n: isize) {
+        let offset = n.unsigned_abs().min(self.rows) * self.words_per_row;
+
+        if n >= 0 {
+            self.words.rotate_right(offset);
+            self.words[..offset].fill(0);
+        } else {
+            self.words.rotate_left(offset);
+            let len = self.words.len();
+            self.words[len - offset..].fill(0);
+        }
+    }
+
+    // Splits a shape row shifted `x` columns to the right into (word index, bits)
+    // pairs, or `None` if it would extend past the last column.
+    fn shape_row_words(&self, bits: u64, x: usize) -> Option<[(usize, u64); 2]> {
+        let width = WORD_BITS - bits.leading_zeros() as usize;
+
+        if bits != 0 && x + width > self.cols {
+            return None;
+        }
+
+        let word = x / WORD_BITS;
+        let shift = x % WORD_BITS;
+
+        let low = bits << shift;
+        let high = if shift == 0 {
+            0
+        } else {
+            bits >> (WORD_BITS - shift)
+        };
+
+        Some([(word, low), (word + 1, high)])
+    }
+
+    // Whether `shape` overlaps a set cell or the edge of the grid when its first row
+    // is placed at row `y`, shifted `x` columns to the right. Bit `i` of each shape
+    // row is column `i` of the shape.
+    #[must_use]
+    pub fn collides(&self, shape: &[u64], x: usize, y: usize) -> bool {
+        shape.iter().enumerate().any(|(dy, &bits)| {
+            if bits == 0 {
+                return false;
+            }
+
+            if y + dy >= self.rows {
+                return true;
+            }
+
+            let row = self.row(y + dy);
+
+            self.shape_row_words(bits, x).is_none_or(|parts| {
+                parts
+                    .into_iter()
+                    .any(|(i, part)| part != 0 && row[i] & part != 0)
+            })
+        })
+    }
+
+    // Sets every cell of `shape` placed as in `collides`. Cells outside of the grid
+    // are ignored.
+    pub fn stamp(&mut self, shape: &[u64], x: usize, y: usize) {
+        let last_word_mask = self.last_word_mask();
+        let words_per_row = self.words_per_row;
+
+        for (dy, &bits) in shape.iter().enumerate() {
+            if y + dy >= self.rows {
+                break;
+            }
+
+            let word = x / WORD_BITS;
+            let shift = x % WORD_BITS;
+            let row = self.row_mut(y + dy);
+
+            if let Some(w) = row.get_mut(word) {
+                *w |= bits << shift;
+            }
+            if shift != 0 {
+                if let Some(w) = row.get_mut(word + 1) {
+                    *w |= bits >> (WORD_BITS - shift);
+                }
+            }
+            if words_per_row > 0 {
+                row[words_per_row - 1] &= last_word_mask;
+            }
+        }
+    }
+
+    fn zip_words(&mut self, other: &BitGrid, op: impl Fn(&mut u64, u64)) {
+        assert_eq!((self.rows, self.cols), (other.rows, other.cols));
+
+        for (word, &other) in self.words.iter_mut().zip(&other.words) {
+            op(word, other);
+        }
+    }
+}
+
+impl BitAndAssign<&BitGrid> for BitGrid {
+    fn bitand_assign(&mut self, rhs: &BitGrid) {
+        self.zip_words(rhs, |w, o| *w &= o);
+    }
+}
+
+impl BitOrAssign<&BitGrid> for BitGrid {
+    fn bitor_assign(&mut self, rhs: &BitGrid) {
+        self.zip_words(rhs, |w, o| *w |= o);
+    }
+}
+
+impl BitXorAssign<&BitGrid> for BitGrid {
+    fn bitxor_assign(&mut self, rhs: &BitGrid) {
+        self.zip_words(rhs, |w, o| *w ^= o);
+    }
+}
+
+impl From<&Grid<bool>> for BitGrid {
+    fn from(grid: &Grid<bool>) -> Self {
+        let mut bits = BitGrid::new(grid.rows(), grid.cols());
+
+        for (p, &val) in grid.iter_points_values() {
+            if val {
+                bits.set(&p, true);
+            }
+        }
+
+        bits
+    }
+}
+
+impl From<&BitGrid> for Grid<bool> {
+    fn from(bits: &BitGrid) -> Self {
+        Grid::new(
+            (0..bits.rows)
+                .map(|y| {
+                    (0..bits.cols)
+                        .map(|x| bits.get(&GPoint::new(x, y)))
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+}
+
+impl Display for BitGrid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.rows {
+            for x in 0..self.cols {
+                let c = if self.get(&GPoint::new(x, y)) {
+                    '#'
+                } else {
+                    '.'
+                };
+                write!(f, "{c}")?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Debug for BitGrid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+    use crate::p2;
+    use crate::vector2::Point2;
+
+    fn parse(s: &str) -> BitGrid {
+        let grid = Grid::new(
+            s.lines()
+                .map(|line| line.chars().map(|c| c == '#').collect())
+                .collect(),
+        );
+
+        BitGrid::from(&grid)
+    }
+
+    #[test]
+    fn test_basic() {
+        let mut g = BitGrid::new(3, 70);
+
+        assert_eq!(g.rows(), 3);
+        assert_eq!(g.cols(), 70);
+        assert!(g.contains(p2!(69, 2)));
+        assert!(!g.contains(p2!(70, 0)));
+
+        g.set(&p2!(0, 0), true);
+        g.set(&p2!(65, 1), true);
+        g.toggle(&p2!(69, 2));
+        assert!(g.get(&p2!(0, 0)));
+        assert!(g.get(&p2!(65, 1)));
+        assert!(g.get(&p2!(69, 2)));
+        assert!(!g.get(&p2!(64, 1)));
+        assert_eq!(g.count_ones(), 3);
+        assert_eq!(g.row_count_ones(1), 1);
+        assert_eq!(g.row(1), &[0, 0b10]);
+
+        g.set(&p2!(0, 0), false);
+        g.toggle(&p2!(69, 2));
+        assert_eq!(g.iter_ones().collect_vec(), vec![p2!(65, 1)]);
+
+        g.push_rows(2);
+        assert_eq!(g.rows(), 5);
+        assert_eq!(g.count_ones(), 1);
+        g.truncate_rows(1);
+        assert_eq!(g.rows(), 1);
+        assert_eq!(g.count_ones(), 0);
+    }
+
+    #[test]
+    fn test_grid_conversion_and_display() {
+        let s = "#..#\n.##.\n";
+        let g = parse(s);
+
+        assert_eq!(g.to_string(), s);
+        assert_eq!(
+            g.iter_ones().collect_vec(),
+            vec![p2!(0, 0), p2!(3, 0), p2!(1, 1), p2!(2, 1)]
+        );
+
+        let grid = Grid::from(&g);
+        assert_eq!(grid.row(1), vec![&false, &true, &true, &false]);
+        assert_eq!(BitGrid::from(&grid), g);
+    }
+
+    #[test]
+    fn test_row_ops() {
+        let mut g = parse("#.#.#\n");
+
+        g.or_row(0, &[0b1110_1000]);
+        assert_eq!(g.to_string(), "#.###\n");
+        g.and_row(0, &[0b1_0110]);
+        assert_eq!(g.to_string(), "..#.#\n");
+        g.xor_row(0, &[u64::MAX]);
+        assert_eq!(g.to_string(), "##.#.\n");
+
+        let mut a = parse("##..\n");
+        let b = parse("#.#.\n");
+        a &= &b;
+        assert_eq!(a.to_string(), "#...\n");
+        a |= &b;
+        assert_eq!(a.to_string(), "#.#.\n");
+        a ^= &parse("####\n");
+        assert_eq!(a.to_string(), ".#.#\n");
+    }
+
+    #[test]
+    fn test_shifts() {
+        let mut g = parse("#..#.\n.#...\n");
+
+        g.shift_cols(1);
+        assert_eq!(g.to_string(), ".#..#\n..#..\n");
+        g.shift_cols(-2);
+        assert_eq!(g.to_string(), "..#..\n#....\n");
+        g.shift_rows(1);
+        assert_eq!(g.to_string(), ".....\n..#..\n");
+        g.shift_rows(-1);
+        assert_eq!(g.to_string(), "..#..\n.....\n");
+        g.shift_rows(5);
+        assert_eq!(g.count_ones(), 0);
+
+        let mut wide = BitGrid::new(1, 130);
+        wide.set(&p2!(60, 0), true);
+        wide.shift_cols(66);
+        assert_eq!(wide.iter_ones().collect_vec(), vec![p2!(126, 0)]);
+        wide.shift_cols(-125);
+        assert_eq!(wide.iter_ones().collect_vec(), vec![p2!(1, 0)]);
+        wide.shift_cols(-2);
+        assert_eq!(wide.count_ones(), 0);
+    }
+
+    #[test]
+    fn test_collides_and_stamp() {
+        let mut g = parse(".......\n...#...\n");
+        let plus = [0b010, 0b111, 0b010];
+
+        assert!(!g.collides(&[0b1111], 0, 0));
+        assert!(g.collides(&[0b1111], 0, 1));
+        assert!(g.collides(&[0b1111], 4, 0));
+        assert!(g.collides(&plus, 0, 0));
+
+        g.push_rows(3);
+        assert!(g.collides(&plus, 2, 0));
+        assert!(!g.collides(&plus, 4, 0));
+        assert!(!g.collides(&plus, 0, 2));
+        assert!(g.collides(&plus, 0, 3));
+
+        g.stamp(&plus, 4, 0);
+        assert_eq!(
+            g.to_string(),
+            ".....#.\n...####\n.....#.\n.......\n.......\n"
+        );
+
+        let mut wide = BitGrid::new(2, 100);
+        assert!(!wide.collides(&[0b11], 63, 0));
+        wide.stamp(&[0b11], 63, 1);
+        assert_eq!(wide.iter_ones().collect_vec(), vec![p2!(63, 1), p2!(64, 1)]);
+        assert!(wide.collides(&[0b1], 64, 1));
+        assert!(wide.collides(&[0b111], 98, 0));
+    }
+}
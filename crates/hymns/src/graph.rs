@@ -0,0 +1,378 @@
+use std::fmt::{Debug, Formatter};
+use std::hash::Hash;
+
+use num_traits::Zero;
+
+use crate::grid::{GPoint, Grid};
+use crate::pathfinding::{bfs_distances, dijkstra_distances, Cost};
+
+mod held_karp;
+mod indexed;
+mod longest;
+
+pub use held_karp::{held_karp, Objective, Route};
+pub use indexed::IndexedGraph;
+pub use longest::longest_simple_path;
+#[cfg(feature = "rayon")]
+pub use longest::par_longest_simple_path;
+
+// Edge weights between nodes identified by index, as read by `held_karp`. `None` means
+// there's no edge.
+pub trait Weights<C> {
+    fn node_count(&self) -> usize;
+    fn weight(&self, from: usize, to: usize) -> Option<C>;
+}
+
+// Shortest distances between `len()` nodes identified by index. `None` means there's no
+// path, and each node is at distance zero from itself.
+#[derive(Clone, Eq, PartialEq)]
+pub struct DistanceMatrix<C> {
+    len: usize,
+    distances: Vec<Option<C>>,
+}
+
+impl<C> DistanceMatrix<C>
+where
+    C: Cost,
+{
+    #[must_use]
+    pub fn new(len: usize) -> Self {
+        let mut distances = vec![None; len * len];
+        for i in 0..len {
+            distances[i * len + i] = Some(C::zero());
+        }
+
+        Self { len, distances }
+    }
+
+    // Builds the matrix from directed edges and closes it with Floyd-Warshall, which
+    // suits small or dense graphs.
+    #[must_use]
+    pub fn floyd_warshall(len: usize, edges: impl IntoIterator<Item = (usize, usize, C)>) -> Self {
+        let mut matrix = Self::new(len);
+
+        for (from, to, cost) in edges {
+            if matrix.get(from, to).is_none_or(|current| cost < current) {
+                matrix.set(from, to, cost);
+            }
+        }
+
+        for k in 0..len {
+            for i in 0..len {
+                let Some(ik) = matrix.get(i, k) else {
+                    continue;
+                };
+
+                for j in 0..len {
+                    if let Some(kj) = matrix.get(k, j) {
+                        if matrix.get(i, j).is_none_or(|ij| ik + kj < ij) {
+                            matrix.set(i, j, ik + kj);
+                        }
+                    }
+                }
+            }
+        }
+
+        matrix
+    }
+
+    // Runs Dijkstra from each of `nodes` and keeps the distances to the others, which
+    // suits sparse graphs where only a few nodes matter.
+    pub fn dijkstra<State, EdgesFn, Edges>(nodes: &[State], get_edges: EdgesFn) -> Self
+    where
+        State: Hash + Eq + Clone,
+        EdgesFn: Fn(&State) -> Edges,
+        Edges: IntoIterator<Item = (State, C)>,
+    {
+        let mut matrix = Self::new(nodes.len());
+
+        for (i, node) in nodes.iter().enumerate() {
+            let distances = dijkstra_distances(std::slice::from_ref(node), &get_edges);
+
+            for (j, other) in nodes.iter().enumerate() {
+                if let Some(&distance) = distances.get(other) {
+                    matrix.set(i, j, distance);
+                }
+            }
+        }
+
+        matrix
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[must_use]
+    pub fn get(&self, from: usize, to: usize) -> Option<C> {
+        self.distances[from * self.len + to]
+    }
+
+    pub fn set(&mut self, from: usize, to: usize, distance: C) {
+        self.distances[from * self.len + to] = Some(distance);
+    }
+
+    pub fn set_symmetric(&mut self, a: usize, b: usize, distance: C) {
+        self.set(a, b, distance);
+        self.set(b, a, distance);
+    }
+
+    #[must_use]
+    pub fn row(&self, from: usize) -> &[Option<C>] {
+        &self.distances[from * self.len..(from + 1) * self.len]
+    }
+
+    // Keeps only `nodes`, in the given order.
+    #[must_use]
+    pub fn select(&self, nodes: &[usize]) -> Self {
+        let mut matrix = Self::new(nodes.len());
+
+        for (i, &from) in nodes.iter().enumerate() {
+            for (j, &to) in nodes.iter().enumerate() {
+                matrix.distances[i * nodes.len() + j] = self.get(from, to);
+            }
+        }
+
+        matrix
+    }
+}
+
+impl DistanceMatrix<usize> {
+    pub fn bfs<State, NextStateFn>(nodes: &[State], get_next_states: NextStateFn) -> Self
+    where
+        State: Hash + Eq + Clone,
+        NextStateFn: Fn(&State) -> Vec<State>,
+    {
+        let mut matrix = Self::new(nodes.len());
+
+        for (i, node) in nodes.iter().enumerate() {
+            let distances = bfs_distances(std::slice::from_ref(node), &get_next_states);
+
+            for (j, other) in nodes.iter().enumerate() {
+                if let Some(&distance) = distances.get(other) {
+                    matrix.set(i, j, distance);
+                }
+            }
+        }
+
+        matrix
+    }
+
+    // Step counts between `points` when moving orthogonally through `passable` cells.
+    pub fn from_grid<T, F>(grid: &Grid<T>, points: &[GPoint], passable: F) -> Self
+    where
+        F: Fn(&T) -> bool,
+    {
+        let mut matrix = Self::new(points.len());
+
+        for (i, point) in points.iter().enumerate() {
+            let distances = grid.distances_from(std::slice::from_ref(point), &passable);
+
+            for (j, other) in points.iter().enumerate() {
+                if let Some(distance) = distances[other] {
+                    matrix.set(i, j, distance);
+                }
+            }
+        }
+
+        matrix
+    }
+}
+
+impl<C> Weights<C> for DistanceMatrix<C>
+where
+    C: Cost,
+{
+    fn node_count(&self) -> usize {
+        self.len
+    }
+
+    fn weight(&self, from: usize, to: usize) -> Option<C> {
+        self.get(from, to)
+    }
+}
+
+impl<C> Debug for DistanceMatrix<C>
+where
+    C: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for i in 0..self.len {
+            let row = &self.distances[i * self.len..(i + 1) * self.len];
+            writeln!(f, "{row:?}")?;
+        }
+
+        Ok(())
+    }
+}
+
+// Arbitrary weights between `len()` nodes identified by index, such as scores that may
+// be negative. Unlike `DistanceMatrix`, every pair has a weight, starting at zero.
+#[derive(Clone, Eq, PartialEq)]
+pub struct Matrix<C> {
+    len: usize,
+    weights: Vec<C>,
+}
+
+impl<C> Matrix<C>
+where
+    C: Copy + Zero,
+{
+    #[must_use]
+    pub fn new(len: usize) -> Self {
+        Self {
+            len,
+            weights: vec![C::zero(); len * len],
+        }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[must_use]
+    pub fn get(&self, from: usize, to: usize) -> C {
+        self.weights[from * self.len + to]
+    }
+
+    pub fn set(&mut self, from: usize, to: usize, weight: C) {
+        self.weights[from * self.len + to] = weight;
+    }
+
+    pub fn set_symmetric(&mut self, a: usize, b: usize, weight: C) {
+        self.set(a, b, weight);
+        self.set(b, a, weight);
+    }
+
+    #[must_use]
+    pub fn row(&self, from: usize) -> &[C] {
+        &self.weights[from * self.len..(from + 1) * self.len]
+    }
+}
+
+impl<C> Weights<C> for Matrix<C>
+where
+    C: Copy + Zero,
+{
+    fn node_count(&self) -> usize {
+        self.len
+    }
+
+    fn weight(&self, from: usize, to: usize) -> Option<C> {
+        Some(self.get(from, to))
+    }
+}
+
+impl<C> Debug for Matrix<C>
+where
+    C: Copy + Zero + Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for i in 0..self.len {
+            writeln!(f, "{:?}", self.row(i))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::p2;
+    use crate::vector2::Point2;
+
+    #[test]
+    fn test_floyd_warshall() {
+        let matrix =
+            DistanceMatrix::floyd_warshall(4, [(0, 1, 5_u32), (1, 2, 3), (0, 2, 10), (2, 0, 1)]);
+
+        assert_eq!(matrix.len(), 4);
+        assert_eq!(matrix.get(0, 2), Some(8));
+        assert_eq!(matrix.get(2, 1), Some(6));
+        assert_eq!(matrix.get(1, 0), Some(4));
+        assert_eq!(matrix.get(3, 3), Some(0));
+        assert_eq!(matrix.get(0, 3), None);
+        assert_eq!(matrix.row(2), &[Some(1), Some(6), Some(0), None]);
+
+        let selected = matrix.select(&[2, 0]);
+        assert_eq!(selected.row(0), &[Some(0), Some(1)]);
+        assert_eq!(selected.row(1), &[Some(8), Some(0)]);
+    }
+
+    #[test]
+    fn test_matrix() {
+        let mut matrix = Matrix::new(3);
+        matrix.set(0, 1, -4_i32);
+        matrix.set_symmetric(1, 2, 7);
+
+        assert_eq!(matrix.len(), 3);
+        assert_eq!(matrix.row(1), &[0, 0, 7]);
+        assert_eq!(matrix.get(0, 1), -4);
+        assert_eq!(matrix.get(1, 0), 0);
+        assert_eq!(matrix.weight(2, 1), Some(7));
+        assert_eq!(matrix.node_count(), 3);
+    }
+
+    #[test]
+    fn test_repeated_searches() {
+        let edges = |&n: &u32| {
+            [(n + 1, 5_u32), (n + 2, 3)]
+                .into_iter()
+                .filter(|&(n, _)| n <= 6)
+        };
+        let matrix = DistanceMatrix::dijkstra(&[0, 3, 6], edges);
+
+        assert_eq!(matrix.row(0), &[Some(0), Some(8), Some(9)]);
+        assert_eq!(matrix.row(1), &[None, Some(0), Some(8)]);
+
+        let matrix = DistanceMatrix::bfs(&[0_u32, 3, 6], |&n| vec![(n + 1) % 7]);
+        assert_eq!(matrix.row(2), &[Some(1), Some(4), Some(0)]);
+    }
+
+    #[test]
+    fn test_from_grid() {
+        let grid: Grid<char> = concat!(
+            "#######\n",
+            "#0.1#.#\n",
+            "#.#...#\n",
+            "#..2#3#\n",
+            "#######\n",
+        )
+        .parse()
+        .unwrap();
+
+        let points = [p2!(1, 1), p2!(3, 1), p2!(3, 3), p2!(5, 3)];
+        let matrix = DistanceMatrix::from_grid(&grid, &points, |&c| c != '#');
+
+        assert_eq!(matrix.get(0, 1), Some(2));
+        assert_eq!(matrix.get(0, 2), Some(4));
+        assert_eq!(matrix.get(3, 2), Some(4));
+        assert_eq!(matrix, {
+            let mut expected = DistanceMatrix::new(4);
+            for (a, b, d) in [
+                (0, 1, 2),
+                (0, 2, 4),
+                (0, 3, 6),
+                (1, 2, 2),
+                (1, 3, 4),
+                (2, 3, 4),
+            ] {
+                expected.set_symmetric(a, b, d);
+            }
+            expected
+        });
+    }
+}
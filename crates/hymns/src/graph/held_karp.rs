@@ -0,0 +1,247 @@
+use crate::graph::Weights;
+use crate::pathfinding::Cost;
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Objective {
+    Min,
+    Max,
+}
+
+impl Objective {
+    fn prefers<C: Copy + Ord>(self, candidate: C, current: C) -> bool {
+        match self {
+            Objective::Min => candidate < current,
+            Objective::Max => candidate > current,
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Route {
+    // Visits every node and returns to the first one.
+    Tour,
+    // Visits every node once, starting and ending anywhere.
+    Path,
+    // Visits every node once, starting at the given node.
+    PathFrom(usize),
+}
+
+// https://en.wikipedia.org/wiki/Held%E2%80%93Karp_algorithm
+//
+// Returns the best total and the order to visit the nodes in, or `None` if missing
+// edges make every route impossible or `Route::PathFrom` names a node that isn't
+// there. Takes O(2^n * n^2) time, so stay below ~20 nodes.
+#[must_use]
+pub fn held_karp<C, W>(weights: &W, objective: Objective, route: Route) -> Option<(C, Vec<usize>)>
+where
+    C: Cost,
+    W: Weights<C>,
+{
+    let n = weights.node_count();
+    if n == 0 || matches!(route, Route::PathFrom(start) if start >= n) {
+        return None;
+    }
+
+    assert!(n < usize::BITS as usize, "too many nodes for a bitmask");
+
+    // best[mask * n + last]: the best cost of visiting `mask`, ending at `last`
+    let mut best: Vec<Option<C>> = vec![None; n << n];
+    let mut parents = vec![usize::MAX; n << n];
+
+    let starts = match route {
+        Route::Tour => 0..1,
+        Route::Path => 0..n,
+        Route::PathFrom(start) => start..start + 1,
+    };
+
+    for start in starts {
+        best[(1 << start) * n + start] = Some(C::zero());
+    }
+
+    for mask in 1_usize..1 << n {
+        for last in (0..n).filter(|last| mask & (1 << last) != 0) {
+            let Some(cost) = best[mask * n + last] else {
+                continue;
+            };
+
+            for next in (0..n).filter(|next| mask & (1 << next) == 0) {
+                let Some(step) = weights.weight(last, next) else {
+                    continue;
+                };
+
+                let index = (mask | (1 << next)) * n + next;
+                let candidate = cost + step;
+
+                if best[index].is_none_or(|current| objective.prefers(candidate, current)) {
+                    best[index] = Some(candidate);
+                    parents[index] = last;
+                }
+            }
+        }
+    }
+
+    let full = (1 << n) - 1;
+
+    let (total, last) = (0..n)
+        .filter_map(|last| {
+            let cost = best[full * n + last]?;
+
+            match route {
+                Route::Tour => weights.weight(last, 0).map(|back| (cost + back, last)),
+                Route::Path | Route::PathFrom(_) => Some((cost, last)),
+            }
+        })
+        .reduce(|current, candidate| {
+            if objective.prefers(candidate.0, current.0) {
+                candidate
+            } else {
+                current
+            }
+        })?;
+
+    let mut order = vec![last];
+    let mut mask = full;
+
+    while parents[mask * n + order[order.len() - 1]] != usize::MAX {
+        let current = order[order.len() - 1];
+        order.push(parents[mask * n + current]);
+        mask &= !(1 << current);
+    }
+
+    order.reverse();
+
+    Some((total, order))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DistanceMatrix, Matrix};
+
+    fn cities() -> DistanceMatrix<u32> {
+        // London, Dublin, Belfast
+        let mut distances = DistanceMatrix::new(3);
+        distances.set_symmetric(0, 1, 464);
+        distances.set_symmetric(0, 2, 518);
+        distances.set_symmetric(1, 2, 141);
+        distances
+    }
+
+    fn route_cost(distances: &DistanceMatrix<u32>, order: &[usize], closed: bool) -> u32 {
+        let mut cost = order
+            .windows(2)
+            .map(|w| distances.get(w[0], w[1]).unwrap())
+            .sum();
+        if closed {
+            cost += distances.get(order[order.len() - 1], order[0]).unwrap();
+        }
+        cost
+    }
+
+    #[test]
+    fn test_paths() {
+        let distances = cities();
+
+        let (cost, order) = held_karp(&distances, Objective::Min, Route::Path).unwrap();
+        assert_eq!(cost, 605);
+        assert_eq!(route_cost(&distances, &order, false), 605);
+        assert_eq!(order.len(), 3);
+
+        let (cost, order) = held_karp(&distances, Objective::Max, Route::Path).unwrap();
+        assert_eq!(cost, 982);
+        assert_eq!(route_cost(&distances, &order, false), 982);
+
+        let (cost, order) = held_karp(&distances, Objective::Min, Route::PathFrom(2)).unwrap();
+        assert_eq!((cost, order), (605, vec![2, 1, 0]));
+    }
+
+    #[test]
+    fn test_tours() {
+        let mut distances = DistanceMatrix::new(5);
+        for a in 0..5 {
+            for b in 0..5 {
+                distances.set(a, b, 10 + u32::try_from((a * 7 + b * 3) % 11).unwrap());
+            }
+        }
+
+        let (cost, order) = held_karp(&distances, Objective::Min, Route::Tour).unwrap();
+        assert_eq!(order[0], 0);
+        assert_eq!(route_cost(&distances, &order, true), cost);
+
+        let mut perms = vec![];
+        let mut stack = vec![vec![0]];
+        while let Some(partial) = stack.pop() {
+            if partial.len() == 5 {
+                perms.push(partial);
+                continue;
+            }
+            for next in (0..5).filter(|n| !partial.contains(n)) {
+                let mut extended = partial.clone();
+                extended.push(next);
+                stack.push(extended);
+            }
+        }
+
+        let brute_min = perms.iter().map(|p| route_cost(&distances, p, true)).min();
+        let brute_max = perms.iter().map(|p| route_cost(&distances, p, true)).max();
+
+        assert_eq!(Some(cost), brute_min);
+        assert_eq!(
+            held_karp(&distances, Objective::Max, Route::Tour).map(|(cost, _)| cost),
+            brute_max
+        );
+    }
+
+    #[test]
+    fn test_missing_edges() {
+        let mut distances = DistanceMatrix::new(3);
+        distances.set(0, 1, 1_u32);
+        distances.set(1, 2, 1);
+
+        assert_eq!(
+            held_karp(&distances, Objective::Min, Route::Path),
+            Some((2, vec![0, 1, 2]))
+        );
+        assert_eq!(held_karp(&distances, Objective::Min, Route::Tour), None);
+        assert_eq!(
+            held_karp(&distances, Objective::Min, Route::PathFrom(1)),
+            None
+        );
+        assert_eq!(
+            held_karp(&DistanceMatrix::<u32>::new(0), Objective::Min, Route::Path),
+            None
+        );
+    }
+
+    #[test]
+    fn test_start_outside() {
+        let distances = cities();
+
+        assert_eq!(
+            held_karp(&distances, Objective::Min, Route::PathFrom(3)),
+            None
+        );
+        assert_eq!(
+            held_karp(&distances, Objective::Min, Route::PathFrom(usize::MAX)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_negative_weights() {
+        let mut weights = Matrix::new(4);
+        weights.set_symmetric(0, 1, -5_i32);
+        weights.set_symmetric(1, 2, 3);
+        weights.set_symmetric(2, 3, -2);
+        weights.set_symmetric(0, 3, 4);
+
+        // 0 and 2 aren't joined, but their weight of zero still makes them neighbors
+        let (cost, order) = held_karp(&weights, Objective::Max, Route::Tour).unwrap();
+        assert_eq!(cost, 7);
+        assert!(order == [0, 2, 1, 3] || order == [0, 3, 1, 2]);
+        assert_eq!(
+            held_karp(&weights, Objective::Min, Route::Path).map(|(cost, _)| cost),
+            Some(-7)
+        );
+    }
+}
@@ -0,0 +1,222 @@
+use std::hash::Hash;
+
+use hashbrown::HashMap;
+
+use crate::grid::{GPoint, Grid};
+use crate::pathfinding::Cost;
+
+// A directed graph whose nodes are numbered in insertion order, so searches can track
+// them in bitmasks or plain vectors instead of hash sets.
+#[derive(Debug, Clone)]
+pub struct IndexedGraph<Node, C> {
+    nodes: Vec<Node>,
+    indices: HashMap<Node, usize>,
+    edges: Vec<Vec<(usize, C)>>,
+}
+
+impl<Node, C> Default for IndexedGraph<Node, C> {
+    fn default() -> Self {
+        Self {
+            nodes: vec![],
+            indices: HashMap::new(),
+            edges: vec![],
+        }
+    }
+}
+
+impl<Node, C> IndexedGraph<Node, C>
+where
+    Node: Hash + Eq + Clone,
+    C: Cost,
+{
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Returns the index of `node`, adding it if it's new.
+    pub fn add_node(&mut self, node: Node) -> usize {
+        if let Some(&index) = self.indices.get(&node) {
+            return index;
+        }
+
+        let index = self.nodes.len();
+        self.indices.insert(node.clone(), index);
+        self.nodes.push(node);
+        self.edges.push(vec![]);
+        index
+    }
+
+    pub fn add_edge(&mut self, from: usize, to: usize, cost: C) {
+        self.edges[from].push((to, cost));
+    }
+
+    pub fn add_undirected_edge(&mut self, a: usize, b: usize, cost: C) {
+        self.add_edge(a, b, cost);
+        self.add_edge(b, a, cost);
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    #[must_use]
+    pub fn node(&self, index: usize) -> &Node {
+        &self.nodes[index]
+    }
+
+    #[must_use]
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    #[must_use]
+    pub fn index_of(&self, node: &Node) -> Option<usize> {
+        self.indices.get(node).copied()
+    }
+
+    #[must_use]
+    pub fn edges(&self, from: usize) -> &[(usize, C)] {
+        &self.edges[from]
+    }
+}
+
+impl IndexedGraph<GPoint, usize> {
+    // Contracts the grid into its junctions: the `keep` points plus every cell with
+    // three or more open neighbors. Corridors between them become edges weighted by
+    // their length, and follow `can_step`, so one-way cells only produce edges going
+    // their way. Corridors that dead-end before reaching a junction are dropped.
+    //
+    // Unlike `Grid::distance_map_from`, `can_step` is also asked about cells that can't
+    // be reached, so it has to reject steps out of blocked cells as well as into them.
+    pub fn from_grid<T, F>(grid: &Grid<T>, keep: &[GPoint], can_step: F) -> Self
+    where
+        F: Fn(&GPoint, &GPoint) -> bool,
+    {
+        let open_neighbors = |p: &GPoint| {
+            grid.all_neighbors(p, false)
+                .into_iter()
+                .filter(|n| can_step(p, n) || can_step(n, p))
+                .collect::<Vec<_>>()
+        };
+
+        let mut graph = Self::new();
+
+        for &p in keep {
+            graph.add_node(p);
+        }
+
+        for p in grid.iter_points() {
+            if open_neighbors(&p).len() > 2 {
+                graph.add_node(p);
+            }
+        }
+
+        for from in 0..graph.len() {
+            let start = *graph.node(from);
+
+            for first in open_neighbors(&start) {
+                if !can_step(&start, &first) {
+                    continue;
+                }
+
+                let (mut prev, mut current, mut length) = (start, first, 1);
+
+                while graph.index_of(&current).is_none() {
+                    let next = open_neighbors(&current)
+                        .into_iter()
+                        .find(|n| *n != prev && can_step(&current, n));
+
+                    let Some(next) = next else {
+                        break;
+                    };
+
+                    (prev, current, length) = (current, next, length + 1);
+                }
+
+                if let Some(to) = graph.index_of(&current) {
+                    if to != from {
+                        graph.add_edge(from, to, length);
+                    }
+                }
+            }
+        }
+
+        graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::p2;
+    use crate::vector2::Point2;
+
+    const MAZE: &str = "\
+#.#####
+#.....#
+#.###.#
+#.#...#
+#.#.#.#
+#...#.#
+#####.#
+";
+
+    #[test]
+    fn test_add_nodes_and_edges() {
+        let mut graph = IndexedGraph::new();
+
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        assert_eq!(graph.add_node("a"), a);
+
+        graph.add_undirected_edge(a, b, 3_u32);
+        graph.add_edge(b, b, 1);
+
+        assert_eq!(graph.len(), 2);
+        assert_eq!(graph.nodes(), &["a", "b"]);
+        assert_eq!(graph.index_of(&"b"), Some(b));
+        assert_eq!(graph.index_of(&"c"), None);
+        assert_eq!(graph.edges(a), &[(b, 3)]);
+        assert_eq!(graph.edges(b), &[(a, 3), (b, 1)]);
+    }
+
+    #[test]
+    fn test_from_grid() {
+        let grid: Grid<char> = MAZE.parse().unwrap();
+        let (start, goal) = (p2!(1, 0), p2!(5, 6));
+
+        let graph = IndexedGraph::from_grid(&grid, &[start, goal], |from, to| {
+            grid[from] != '#' && grid[to] != '#'
+        });
+
+        assert_eq!(graph.nodes(), &[start, goal, p2!(1, 1), p2!(5, 3)]);
+        assert_eq!(graph.edges(0), &[(2, 1)]);
+        assert_eq!(graph.edges(1), &[(3, 3)]);
+
+        let mut edges = graph.edges(2).to_vec();
+        edges.sort_unstable();
+        assert_eq!(edges, vec![(0, 1), (3, 6), (3, 10)]);
+    }
+
+    #[test]
+    fn test_from_grid_one_way() {
+        let grid: Grid<char> = "#.###\n#>..#\n###.#\n".parse().unwrap();
+        let (start, goal) = (p2!(1, 0), p2!(3, 2));
+
+        let can_step = |from: &GPoint, to: &GPoint| {
+            grid[from] != '#' && grid[to] != '#' && (grid[from] != '>' || to.x > from.x)
+        };
+
+        let graph = IndexedGraph::from_grid(&grid, &[start, goal], can_step);
+
+        assert_eq!(graph.edges(0), &[(1, 4)]);
+        assert!(graph.edges(1).is_empty());
+    }
+}
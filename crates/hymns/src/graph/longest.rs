@@ -0,0 +1,308 @@
+use std::hash::Hash;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::graph::IndexedGraph;
+use crate::pathfinding::Cost;
+
+// How many partial paths to spread across threads before searching each one sequentially.
+#[cfg(feature = "rayon")]
+const PARALLEL_BRANCHES: usize = 256;
+
+trait Mask: Copy + Send + Sync {
+    const EMPTY: Self;
+
+    fn with(self, index: usize) -> Self;
+    fn contains(self, index: usize) -> bool;
+}
+
+macro_rules! impl_mask {
+    ($($t:ty),*) => {
+        $(
+            impl Mask for $t {
+                const EMPTY: Self = 0;
+
+                fn with(self, index: usize) -> Self {
+                    self | (1 << index)
+                }
+
+                fn contains(self, index: usize) -> bool {
+                    self & (1 << index) != 0
+                }
+            }
+        )*
+    };
+}
+
+impl_mask!(u64, u128);
+
+struct Search<'a, Node, C> {
+    graph: &'a IndexedGraph<Node, C>,
+    goal: usize,
+    // If only one node leads into the goal, any path reaching it has to go to the goal
+    // next: leaving anywhere else would cut the goal off for good.
+    last_step: Option<usize>,
+}
+
+impl<'a, Node, C> Search<'a, Node, C>
+where
+    Node: Hash + Eq + Clone,
+    C: Cost,
+{
+    fn new(graph: &'a IndexedGraph<Node, C>, goal: usize) -> Self {
+        assert!(
+            graph.len() <= 128,
+            "longest_simple_path supports at most 128 nodes, got {}",
+            graph.len()
+        );
+
+        let mut into_goal = (0..graph.len())
+            .filter(|&from| from != goal && graph.edges(from).iter().any(|(to, _)| *to == goal));
+
+        let last_step = match (into_goal.next(), into_goal.next()) {
+            (Some(only), None) => Some(only),
+            _ => None,
+        };
+
+        Self {
+            graph,
+            goal,
+            last_step,
+        }
+    }
+
+    fn edges(&self, from: usize) -> impl Iterator<Item = &(usize, C)> + '_ {
+        let forced = self.last_step == Some(from);
+
+        self.graph
+            .edges(from)
+            .iter()
+            .filter(move |(to, _)| !forced || *to == self.goal)
+    }
+
+    fn dfs<M: Mask>(&self, seen: M, from: usize) -> Option<C> {
+        if from == self.goal {
+            return Some(C::zero());
+        }
+
+        let seen = seen.with(from);
+
+        self.edges(from)
+            .filter(|(to, _)| !seen.contains(*to))
+            .filter_map(|&(to, cost)| Some(cost + self.dfs(seen, to)?))
+            .max()
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<Node, C> Search<'_, Node, C>
+where
+    Node: Hash + Eq + Clone + Sync,
+    C: Cost + Send + Sync,
+{
+    fn par_dfs<M: Mask>(&self, start: usize) -> Option<C> {
+        let mut best = None;
+        let mut frontier = vec![(M::EMPTY, start, C::zero())];
+
+        while !frontier.is_empty() && frontier.len() < PARALLEL_BRANCHES {
+            let mut next = vec![];
+
+            for (seen, from, cost) in frontier {
+                if from == self.goal {
+                    best = best.max(Some(cost));
+                    continue;
+                }
+
+                let seen = seen.with(from);
+
+                for &(to, step) in self.edges(from) {
+                    if !seen.contains(to) {
+                        next.push((seen, to, cost + step));
+                    }
+                }
+            }
+
+            frontier = next;
+        }
+
+        frontier
+            .into_par_iter()
+            .filter_map(|(seen, from, cost)| Some(cost + self.dfs(seen, from)?))
+            .max()
+            .max(best)
+    }
+}
+
+// The most expensive path from `start` to `goal` that visits each node at most once,
+// found by exhaustive search. Only practical on small graphs, such as contracted grids,
+// and limited to 128 nodes so the visited set fits in a bitmask.
+#[must_use]
+pub fn longest_simple_path<Node, C>(
+    graph: &IndexedGraph<Node, C>,
+    start: usize,
+    goal: usize,
+) -> Option<C>
+where
+    Node: Hash + Eq + Clone,
+    C: Cost,
+{
+    let search = Search::new(graph, goal);
+
+    if graph.len() <= 64 {
+        search.dfs(u64::EMPTY, start)
+    } else {
+        search.dfs(u128::EMPTY, start)
+    }
+}
+
+#[cfg(feature = "rayon")]
+#[must_use]
+pub fn par_longest_simple_path<Node, C>(
+    graph: &IndexedGraph<Node, C>,
+    start: usize,
+    goal: usize,
+) -> Option<C>
+where
+    Node: Hash + Eq + Clone + Sync,
+    C: Cost + Send + Sync,
+{
+    let search = Search::new(graph, goal);
+
+    if graph.len() <= 64 {
+        search.par_dfs::<u64>(start)
+    } else {
+        search.par_dfs::<u128>(start)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A grid of `width` x `height` nodes joined to their right and lower neighbors,
+    // with costs that vary enough to make the longest path unique-ish.
+    fn lattice(width: usize, height: usize) -> IndexedGraph<(usize, usize), u32> {
+        let mut graph = IndexedGraph::new();
+
+        for y in 0..height {
+            for x in 0..width {
+                let node = graph.add_node((x, y));
+                let cost = u32::try_from(1 + (x * 3 + y * 5) % 7).unwrap();
+
+                if x > 0 {
+                    let left = graph.index_of(&(x - 1, y)).unwrap();
+                    graph.add_undirected_edge(node, left, cost);
+                }
+                if y > 0 {
+                    let up = graph.index_of(&(x, y - 1)).unwrap();
+                    graph.add_undirected_edge(node, up, cost + 1);
+                }
+            }
+        }
+
+        graph
+    }
+
+    // A chain of `len` nodes with a shortcut over every tenth node.
+    fn chain(len: usize) -> IndexedGraph<(usize, usize), u32> {
+        let mut graph = IndexedGraph::new();
+
+        for i in 0..len {
+            let node = graph.add_node((i, 0));
+            if i > 0 {
+                graph.add_undirected_edge(node - 1, node, 2);
+            }
+            if i >= 2 && i % 10 == 2 {
+                graph.add_undirected_edge(node - 2, node, 3);
+            }
+        }
+
+        graph
+    }
+
+    fn brute_force(
+        graph: &IndexedGraph<(usize, usize), u32>,
+        from: usize,
+        goal: usize,
+    ) -> Option<u32> {
+        fn go(
+            graph: &IndexedGraph<(usize, usize), u32>,
+            seen: &mut Vec<bool>,
+            from: usize,
+            goal: usize,
+        ) -> Option<u32> {
+            if from == goal {
+                return Some(0);
+            }
+
+            seen[from] = true;
+
+            let mut best = None;
+            for &(to, cost) in graph.edges(from) {
+                if !seen[to] {
+                    best = best.max(go(graph, seen, to, goal).map(|rest| cost + rest));
+                }
+            }
+
+            seen[from] = false;
+
+            best
+        }
+
+        go(graph, &mut vec![false; graph.len()], from, goal)
+    }
+
+    #[test]
+    fn test_longest_simple_path() {
+        let graph = lattice(4, 3);
+        let goal = graph.len() - 1;
+
+        assert_eq!(
+            longest_simple_path(&graph, 0, goal),
+            brute_force(&graph, 0, goal)
+        );
+        assert_eq!(longest_simple_path(&graph, 5, 5), Some(0));
+    }
+
+    #[test]
+    fn test_forced_last_step() {
+        // the goal hangs off node 3, so reaching 3 has to end the path
+        let mut graph = IndexedGraph::new();
+        for node in 0..5 {
+            graph.add_node(node);
+        }
+        graph.add_undirected_edge(0, 1, 1_u32);
+        graph.add_undirected_edge(0, 2, 1);
+        graph.add_undirected_edge(1, 2, 1);
+        graph.add_undirected_edge(1, 3, 10);
+        graph.add_undirected_edge(3, 4, 1);
+
+        assert_eq!(longest_simple_path(&graph, 0, 4), Some(13));
+        assert_eq!(longest_simple_path(&graph, 4, 0), Some(13));
+
+        graph.add_node(5);
+        assert_eq!(longest_simple_path(&graph, 0, 5), None);
+    }
+
+    #[test]
+    fn test_wide_masks() {
+        let graph = chain(100);
+
+        assert_eq!(longest_simple_path(&graph, 0, 99), Some(198));
+        assert_eq!(brute_force(&graph, 0, 99), Some(198));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parallel() {
+        for graph in [lattice(4, 4), chain(100)] {
+            let goal = graph.len() - 1;
+
+            assert_eq!(
+                par_longest_simple_path(&graph, 0, goal),
+                longest_simple_path(&graph, 0, goal)
+            );
+        }
+    }
+}
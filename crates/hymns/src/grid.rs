@@ -1,26 +1,108 @@
 use std::borrow::Borrow;
 use std::fmt::{Debug, Display, Formatter};
 use std::hash::{Hash, Hasher};
-use std::ops::{Index, IndexMut};
-use std::str::FromStr;
+use std::ops::{AddAssign, Index, IndexMut};
 
 use itertools::Itertools;
+use num_traits::PrimInt;
 
 use crate::p2;
 use crate::vector2::{Direction, Point2};
 
+mod distance;
+mod flood;
+mod image;
+mod parse;
+mod pattern;
+mod ray;
+mod symmetry;
+mod transform;
+
+pub use distance::DistanceMap;
+pub use flood::{Component, Components};
+pub use hymns_derive::GridCell;
+pub use image::ImageFormat;
+pub use parse::{Markers, ParseGridError};
+pub use transform::Symmetry;
+
 pub type GPoint = Point2<usize>;
+pub type SPoint = Point2<isize>;
+
+// The integer types a grid can be indexed by.
+pub trait Coord: PrimInt + AddAssign {}
+
+impl<C: PrimInt + AddAssign> Coord for C {}
 
+// Cells are stored from the top-left corner, which sits at `origin` in the caller's
+// coordinates. Grids indexed by `GPoint` normally keep the origin at zero, while ones
+// indexed by `SPoint` can be placed anywhere, including at negative coordinates.
 #[derive(Eq, PartialEq, Clone)]
-pub struct Grid<T> {
+pub struct Grid<T, C: Coord = usize> {
     elems: Vec<Vec<T>>,
+    origin: Point2<C>,
 }
 
 impl<T> Grid<T> {
     #[must_use]
     pub fn new(elems: Vec<Vec<T>>) -> Self {
         debug_assert!(elems.iter().skip(1).all(|row| row.len() == elems[0].len()));
-        Self { elems }
+        Self {
+            elems,
+            origin: Point2::origin(),
+        }
+    }
+
+    // Moves the top-left cell to `origin`, which also picks the coordinate type the
+    // grid is indexed by from then on.
+    #[must_use]
+    pub fn with_origin<C: Coord>(self, origin: Point2<C>) -> Grid<T, C> {
+        Grid {
+            elems: self.elems,
+            origin,
+        }
+    }
+}
+
+impl<T, C: Coord> Grid<T, C> {
+    // Covers `min..=max` on both axes.
+    #[must_use]
+    pub fn from_bounds(min: Point2<C>, max: Point2<C>, value: T) -> Self
+    where
+        T: Clone,
+    {
+        let cols = (max.x - min.x).to_usize().unwrap() + 1;
+        let rows = (max.y - min.y).to_usize().unwrap() + 1;
+
+        Grid::new(vec![vec![value; cols]; rows]).with_origin(min)
+    }
+
+    #[must_use]
+    pub fn origin(&self) -> Point2<C> {
+        self.origin
+    }
+
+    // The bottom-right cell, inclusive, or `None` if the grid has no cells.
+    #[must_use]
+    pub fn max(&self) -> Option<Point2<C>> {
+        let x = self.cols().checked_sub(1)?;
+        let y = self.rows().checked_sub(1)?;
+        Some(self.to_world(p2!(x, y)))
+    }
+
+    // Converts a point in the caller's coordinates to the row and column it's stored
+    // at, if it's inside the grid.
+    #[must_use]
+    pub fn to_local(&self, p: Point2<C>) -> Option<GPoint> {
+        self.to_unchecked_local(p)
+            .filter(|p| p.x < self.cols() && p.y < self.rows())
+    }
+
+    #[must_use]
+    pub fn to_world(&self, p: GPoint) -> Point2<C> {
+        p2!(
+            self.origin.x + C::from(p.x).unwrap(),
+            self.origin.y + C::from(p.y).unwrap()
+        )
     }
 
     #[must_use]
@@ -29,8 +111,8 @@ impl<T> Grid<T> {
     }
 
     #[must_use]
-    pub fn contains(&self, point: GPoint) -> bool {
-        point.y < self.rows() && point.x < self.cols()
+    pub fn contains(&self, point: Point2<C>) -> bool {
+        self.to_local(point).is_some()
     }
 
     #[must_use]
@@ -38,14 +120,17 @@ impl<T> Grid<T> {
         self.elems.first().map_or(0, Vec::len)
     }
 
+    // The cells at `y` and `x` respectively, in the caller's coordinates.
     #[must_use]
-    pub fn row(&self, row: usize) -> Vec<&T> {
-        self.elems[row].iter().collect_vec()
+    pub fn row(&self, y: C) -> Vec<&T> {
+        let y = (y - self.origin.y).to_usize().unwrap();
+        self.elems[y].iter().collect_vec()
     }
 
     #[must_use]
-    pub fn col(&self, col: usize) -> Vec<&T> {
-        self.elems.iter().map(|row| &row[col]).collect_vec()
+    pub fn col(&self, x: C) -> Vec<&T> {
+        let x = (x - self.origin.x).to_usize().unwrap();
+        self.elems.iter().map(|row| &row[x]).collect_vec()
     }
 
     pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> {
@@ -80,75 +165,141 @@ impl<T> Grid<T> {
         })
     }
 
-    pub fn iter_points(&self) -> impl Iterator<Item = GPoint> + '_ {
-        (0..self.rows()).flat_map(|y| (0..self.cols()).map(move |x| p2!(x, y)))
+    pub fn iter_points(&self) -> impl Iterator<Item = Point2<C>> + '_ {
+        (0..self.rows()).flat_map(move |y| (0..self.cols()).map(move |x| self.to_world(p2!(x, y))))
     }
 
-    pub fn iter_points_values(&self) -> impl Iterator<Item = (GPoint, &T)> + '_ {
-        self.iter_points().map(|p| (p, &self[p]))
+    pub fn iter_points_values(&self) -> impl Iterator<Item = (Point2<C>, &T)> + '_ {
+        self.iter_points().zip(self.iter_values())
     }
 
-    pub fn into_iter_points_values(self) -> impl Iterator<Item = (GPoint, T)> {
+    pub fn into_iter_points_values(self) -> impl Iterator<Item = (Point2<C>, T)> {
         let cols = self.cols();
+        let origin = self.origin;
 
         self.into_iter_values().enumerate().map(move |(i, val)| {
-            let y = i / cols;
-            let x = i % cols;
-            (p2!(x, y), val)
+            let y = C::from(i / cols).unwrap();
+            let x = C::from(i % cols).unwrap();
+            (p2!(origin.x + x, origin.y + y), val)
         })
     }
 
     #[must_use]
-    pub fn get_value(&self, p: &GPoint) -> Option<&T> {
+    pub fn get_value(&self, p: &Point2<C>) -> Option<&T> {
+        let p = self.to_unchecked_local(*p)?;
         self.elems.get(p.y).and_then(|row| row.get(p.x))
     }
 
-    pub fn get_value_mut(&mut self, p: &GPoint) -> Option<&mut T> {
+    pub fn get_value_mut(&mut self, p: &Point2<C>) -> Option<&mut T> {
+        let p = self.to_unchecked_local(*p)?;
         self.elems.get_mut(p.y).and_then(|row| row.get_mut(p.x))
     }
 
-    pub fn set_value(&mut self, p: &GPoint, val: T) {
-        self.elems[p.y][p.x] = val;
+    pub fn set_value(&mut self, p: &Point2<C>, val: T) {
+        self[p] = val;
     }
 
     #[must_use]
-    pub fn all_neighbors(&self, p: &GPoint, extended: bool) -> Vec<GPoint> {
+    pub fn all_neighbors(&self, p: &Point2<C>, extended: bool) -> Vec<Point2<C>> {
         let mut neighbors = p.all_neighbors(extended, false);
-        neighbors.retain(|p| p.y < self.rows() && p.x < self.cols());
+        neighbors.retain(|&p| self.contains(p));
         neighbors
     }
 
     pub fn iter_all_neighbors(
         &self,
-        point2: &GPoint,
+        point2: &Point2<C>,
         extended: bool,
-    ) -> impl Iterator<Item = (GPoint, &T)> + '_ {
+    ) -> impl Iterator<Item = (Point2<C>, &T)> + '_ {
         self.all_neighbors(point2, extended)
             .into_iter()
             .map(move |p| (p, &self[p]))
     }
 
     #[must_use]
-    pub fn get_neighbors(&self, p: &GPoint, directions: &[Direction]) -> Vec<GPoint> {
+    pub fn get_neighbors(&self, p: &Point2<C>, directions: &[Direction]) -> Vec<Point2<C>> {
         let mut neighbors = Vec::with_capacity(directions.len());
 
-        neighbors.extend(directions.iter().filter_map(|shift| {
-            p.shifted(*shift)
-                .filter(|p| p.y < self.rows() && p.x < self.cols())
-        }));
+        neighbors.extend(
+            directions
+                .iter()
+                .filter_map(|shift| p.shifted(*shift).filter(|&p| self.contains(p))),
+        );
 
         neighbors
     }
 
     #[must_use]
-    pub fn get_neighbor(&self, p: &GPoint, direction: Direction) -> Option<GPoint> {
+    pub fn get_neighbor(&self, p: &Point2<C>, direction: Direction) -> Option<Point2<C>> {
         self.get_neighbors(p, &[direction]).first().copied()
     }
+
+    // Like `to_local`, but leaves the bounds check to the caller. Grids with their
+    // origin at zero, which is nearly all of them, skip the offset arithmetic.
+    #[inline]
+    fn to_unchecked_local(&self, p: Point2<C>) -> Option<GPoint> {
+        if self.origin.x.is_zero() && self.origin.y.is_zero() {
+            return Some(p2!(p.x.to_usize()?, p.y.to_usize()?));
+        }
+
+        let x = p.x.checked_sub(&self.origin.x)?.to_usize()?;
+        let y = p.y.checked_sub(&self.origin.y)?.to_usize()?;
+
+        Some(p2!(x, y))
+    }
+
+    // Zero-origin points are left for `elems` to bounds check, while points on grids
+    // with an offset get a message in the caller's coordinates.
+    #[inline]
+    fn local_or_panic(&self, p: Point2<C>) -> GPoint {
+        let local = if self.origin.x.is_zero() && self.origin.y.is_zero() {
+            self.to_unchecked_local(p)
+        } else {
+            self.to_local(p)
+        };
+
+        local.unwrap_or_else(|| {
+            panic!(
+                "{:?} is outside the grid",
+                (p.x.to_i128().unwrap(), p.y.to_i128().unwrap())
+            )
+        })
+    }
+}
+
+impl<C: Coord> Grid<bool, C> {
+    // The bounding box of `points`, with those points set.
+    #[must_use]
+    pub fn from_points<'a>(points: impl IntoIterator<Item = &'a Point2<C>>) -> Self
+    where
+        C: 'a,
+    {
+        let points = points.into_iter().copied().collect_vec();
+
+        let Some(&first) = points.first() else {
+            return Grid::new(vec![]).with_origin(Point2::origin());
+        };
+
+        let (min, max) = points.iter().fold((first, first), |(min, max), p| {
+            (
+                p2!(min.x.min(p.x), min.y.min(p.y)),
+                p2!(max.x.max(p.x), max.y.max(p.y)),
+            )
+        });
+
+        let mut grid = Self::from_bounds(min, max, false);
+        for p in points {
+            grid[p] = true;
+        }
+
+        grid
+    }
 }
 
-impl<T> Display for Grid<T>
+impl<T, C> Display for Grid<T, C>
 where
     T: Display,
+    C: Coord,
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         for row in self.iter_rows() {
@@ -162,9 +313,10 @@ where
     }
 }
 
-impl<T> Debug for Grid<T>
+impl<T, C> Debug for Grid<T, C>
 where
     T: Debug,
+    C: Coord,
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         for row in self.iter_rows() {
@@ -178,38 +330,10 @@ where
     }
 }
 
-impl<T> FromStr for Grid<T>
-where
-    T: From<char>,
-{
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self::new(
-            s.lines()
-                .map(|line| line.chars().map(|c| T::from(c)).collect_vec())
-                .collect_vec(),
-        ))
-    }
-}
-
-impl<T> TryFrom<&str> for Grid<T>
-where
-    T: TryFrom<char>,
-{
-    type Error = ();
-
-    fn try_from(s: &str) -> Result<Self, Self::Error> {
-        s.lines()
-            .map(|line| line.chars().map(T::try_from).try_collect().map_err(|_| ()))
-            .try_collect()
-            .map(Self::new)
-    }
-}
-
-impl<T> Hash for Grid<T>
+impl<T, C> Hash for Grid<T, C>
 where
     T: Hash + Eq,
+    C: Coord,
 {
     fn hash<H: Hasher>(&self, state: &mut H) {
         for row in self.iter_rows() {
@@ -218,24 +342,26 @@ where
     }
 }
 
-impl<T, U> Index<U> for Grid<T>
+impl<T, C, U> Index<U> for Grid<T, C>
 where
-    U: Borrow<GPoint>,
+    C: Coord,
+    U: Borrow<Point2<C>>,
 {
     type Output = T;
 
     fn index(&self, p: U) -> &Self::Output {
-        let p = p.borrow();
+        let p = self.local_or_panic(*p.borrow());
         &self.elems[p.y][p.x]
     }
 }
 
-impl<T, U> IndexMut<U> for Grid<T>
+impl<T, C, U> IndexMut<U> for Grid<T, C>
 where
-    U: Borrow<GPoint>,
+    C: Coord,
+    U: Borrow<Point2<C>>,
 {
     fn index_mut(&mut self, p: U) -> &mut Self::Output {
-        let p = p.borrow();
+        let p = self.local_or_panic(*p.borrow());
         &mut self.elems[p.y][p.x]
     }
 }
@@ -449,6 +575,97 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_origin() {
+        let mut g = Grid::from_bounds(p2!(-2, 495), p2!(1, 497), '.');
+
+        assert_eq!((g.rows(), g.cols()), (3, 4));
+        assert_eq!(g.origin(), p2!(-2, 495));
+        assert_eq!(g.max(), Some(p2!(1, 497)));
+
+        g[p2!(-2, 495)] = '#';
+        g[&p2!(1, 497)] = '#';
+        assert_eq!(g.to_string(), "#...\n....\n...#\n");
+
+        assert!(g.contains(p2!(0, 496)));
+        assert!(!g.contains(p2!(-3, 496)));
+        assert!(!g.contains(p2!(2, 496)));
+        assert!(!g.contains(p2!(0, 0)));
+        assert_eq!(g.get_value(&p2!(-3, 495)), None);
+        assert_eq!(g.row(497), vec![&'.', &'.', &'.', &'#']);
+        assert_eq!(g.col(-2), vec![&'#', &'.', &'.']);
+
+        assert_eq!(g.to_local(p2!(-1, 496)), Some(p2!(1, 1)));
+        assert_eq!(g.to_world(p2!(1, 1)), p2!(-1, 496));
+
+        assert_eq!(
+            g.iter_points_values()
+                .filter(|&(_, &c)| c == '#')
+                .map(|(p, _)| p)
+                .collect::<Vec<_>>(),
+            vec![p2!(-2, 495), p2!(1, 497)]
+        );
+        assert_eq!(
+            g.clone().into_iter_points_values().last(),
+            Some((p2!(1, 497), '#'))
+        );
+
+        let g = Grid::new(vec![vec![0, 1], vec![2, 3]]).with_origin(p2!(-1_isize, -1));
+        assert_eq!(g[p2!(0, 0)], 3);
+        assert_eq!(
+            g.iter_points().collect::<Vec<_>>(),
+            vec![p2!(-1, -1), p2!(0, -1), p2!(-1, 0), p2!(0, 0)]
+        );
+    }
+
+    #[test]
+    fn test_origin_neighbors() {
+        let g = Grid::from_bounds(p2!(-1, -1), p2!(1, 1), 0);
+
+        assert_eq!(g.all_neighbors(&p2!(0, 0), true).len(), 8);
+        assert_eq!(
+            g.get_neighbors(&p2!(-1, -1), &[Up, DownRight, Down]),
+            vec![p2!(0, 0), p2!(-1, 0)]
+        );
+        assert_eq!(g.get_neighbor(&p2!(1, 1), Down), None);
+        assert_eq!(g.all_neighbors(&p2!(1, 1), false).len(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "(-1, 0) is outside the grid")]
+    fn test_index_outside() {
+        let g = Grid::from_bounds(p2!(0, 0), p2!(1, 1), 0);
+        let _ = g[p2!(-1, 0)];
+    }
+
+    #[test]
+    fn test_max_empty() {
+        assert_eq!(Grid::<char>::new(vec![]).max(), None);
+        assert_eq!(Grid::<char>::new(vec![vec![]]).max(), None);
+        assert_eq!(Grid::new(vec![vec!['.']]).max(), Some(p2!(0, 0)));
+    }
+
+    #[test]
+    fn test_from_points() {
+        let g = Grid::from_points(&[p2!(-1, 2), p2!(1, 3)]);
+
+        assert_eq!(g.origin(), p2!(-1, 2));
+        assert_eq!(g.max(), Some(p2!(1, 3)));
+        assert_eq!((g.rows(), g.cols()), (2, 3));
+
+        assert!(g[p2!(-1, 2)]);
+        assert!(g[p2!(1, 3)]);
+        assert!(!g[p2!(0, 2)]);
+        assert!(!g[p2!(1, 2)]);
+        assert!(!g[p2!(-1, 3)]);
+        assert!(!g[p2!(0, 3)]);
+        assert_eq!(g.get_value(&p2!(-2, 2)), None);
+
+        let g = Grid::<bool, isize>::from_points(&[]);
+        assert_eq!((g.rows(), g.cols()), (0, 0));
+        assert_eq!(g.max(), None);
+    }
+
     #[test]
     fn test_display_debug() {
         let g = Grid::new(vec![vec!["0", "1"], vec!["2", "3"]]);
@@ -0,0 +1,126 @@
+use std::collections::VecDeque;
+
+use crate::grid::{GPoint, Grid};
+
+#[derive(Debug, Clone)]
+pub struct DistanceMap {
+    pub distances: Grid<Option<usize>>,
+    parents: Grid<Option<GPoint>>,
+}
+
+impl DistanceMap {
+    #[must_use]
+    pub fn distance(&self, p: &GPoint) -> Option<usize> {
+        self.distances.get_value(p).copied().flatten()
+    }
+
+    #[must_use]
+    pub fn path_to(&self, target: &GPoint) -> Option<Vec<GPoint>> {
+        self.distance(target)?;
+
+        let mut path = vec![*target];
+        let mut current = *target;
+
+        while let Some(parent) = self.parents[current] {
+            path.push(parent);
+            current = parent;
+        }
+
+        path.reverse();
+        Some(path)
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn distances_from<F>(&self, sources: &[GPoint], passable: F) -> Grid<Option<usize>>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.distance_map_from(sources, |_, to| passable(&self[to]))
+            .distances
+    }
+
+    pub fn distance_map_from<F>(&self, sources: &[GPoint], can_step: F) -> DistanceMap
+    where
+        F: Fn(&GPoint, &GPoint) -> bool,
+    {
+        let mut distances = Grid::new(vec![vec![None; self.cols()]; self.rows()]);
+        let mut parents = Grid::new(vec![vec![None; self.cols()]; self.rows()]);
+        let mut frontier = VecDeque::new();
+
+        for &source in sources.iter().filter(|p| self.contains(**p)) {
+            if distances[source].is_none() {
+                distances[source] = Some(0);
+                frontier.push_back(source);
+            }
+        }
+
+        while let Some(current) = frontier.pop_front() {
+            let distance = distances[current].unwrap() + 1;
+
+            for neighbor in self.all_neighbors(&current, false) {
+                if distances[neighbor].is_none() && can_step(&current, &neighbor) {
+                    distances[neighbor] = Some(distance);
+                    parents[neighbor] = Some(current);
+                    frontier.push_back(neighbor);
+                }
+            }
+        }
+
+        DistanceMap { distances, parents }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::p2;
+    use crate::vector2::Point2;
+
+    #[test]
+    fn test_distances_from() {
+        let g: Grid<char> = "..#\n.##\n...\n".parse().unwrap();
+
+        let distances = g.distances_from(&[p2!(0, 0)], |&c| c == '.');
+        assert_eq!(
+            distances,
+            Grid::new(vec![
+                vec![Some(0), Some(1), None],
+                vec![Some(1), None, None],
+                vec![Some(2), Some(3), Some(4)],
+            ])
+        );
+
+        let distances = g.distances_from(&[p2!(0, 0), p2!(2, 2)], |&c| c == '.');
+        assert_eq!(distances[p2!(1, 2)], Some(1));
+        assert_eq!(distances[p2!(0, 2)], Some(2));
+
+        let distances = g.distances_from(&[p2!(5, 5)], |_| true);
+        assert!(distances.iter_values().all(Option::is_none));
+    }
+
+    #[test]
+    fn test_distance_map() {
+        let g = Grid::new(vec![vec![0, 1, 2], vec![5, 4, 3], vec![9, 9, 9]]);
+
+        let map = g.distance_map_from(&[p2!(0, 0)], |from, to| g[to] <= g[from] + 1);
+
+        assert_eq!(map.distance(&p2!(0, 1)), Some(5));
+        assert_eq!(map.distance(&p2!(0, 2)), None);
+        assert_eq!(map.distance(&p2!(9, 9)), None);
+
+        assert_eq!(
+            map.path_to(&p2!(0, 1)),
+            Some(vec![
+                p2!(0, 0),
+                p2!(1, 0),
+                p2!(2, 0),
+                p2!(2, 1),
+                p2!(1, 1),
+                p2!(0, 1),
+            ])
+        );
+        assert_eq!(map.path_to(&p2!(0, 0)), Some(vec![p2!(0, 0)]));
+        assert_eq!(map.path_to(&p2!(1, 2)), None);
+    }
+}
@@ -0,0 +1,195 @@
+use std::collections::VecDeque;
+
+use hashbrown::HashSet;
+
+use crate::grid::{GPoint, Grid};
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Component {
+    pub size: usize,
+    pub min: GPoint,
+    pub max: GPoint,
+}
+
+impl Component {
+    fn new(start: GPoint) -> Self {
+        Self {
+            size: 0,
+            min: start,
+            max: start,
+        }
+    }
+
+    fn add(&mut self, p: GPoint) {
+        self.size += 1;
+        self.min.x = self.min.x.min(p.x);
+        self.min.y = self.min.y.min(p.y);
+        self.max.x = self.max.x.max(p.x);
+        self.max.y = self.max.y.max(p.y);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Components {
+    pub labels: Grid<Option<usize>>,
+    pub components: Vec<Component>,
+}
+
+impl Components {
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+
+    #[must_use]
+    pub fn label(&self, p: &GPoint) -> Option<usize> {
+        self.labels.get_value(p).copied().flatten()
+    }
+
+    pub fn sizes(&self) -> impl Iterator<Item = usize> + '_ {
+        self.components.iter().map(|c| c.size)
+    }
+
+    pub fn points(&self, label: usize) -> impl Iterator<Item = GPoint> + '_ {
+        let Component { min, max, .. } = self.components[label];
+
+        (min.y..=max.y)
+            .flat_map(move |y| (min.x..=max.x).map(move |x| GPoint::new(x, y)))
+            .filter(move |p| self.labels[p] == Some(label))
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn flood_fill<F>(&self, start: GPoint, predicate: F) -> HashSet<GPoint>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let mut seen = HashSet::new();
+
+        if !self.get_value(&start).is_some_and(&predicate) {
+            return seen;
+        }
+
+        let mut frontier = VecDeque::from([start]);
+        seen.insert(start);
+
+        while let Some(current) = frontier.pop_front() {
+            for neighbor in self.all_neighbors(&current, false) {
+                if predicate(&self[neighbor]) && seen.insert(neighbor) {
+                    frontier.push_back(neighbor);
+                }
+            }
+        }
+
+        seen
+    }
+
+    pub fn components<F>(&self, predicate: F, extended_neighbors: bool) -> Components
+    where
+        F: Fn(&T) -> bool,
+    {
+        let mut labels = Grid::new(vec![vec![None; self.cols()]; self.rows()]);
+        let mut components = vec![];
+        let mut frontier = VecDeque::new();
+
+        for start in self.iter_points() {
+            if labels[start].is_some() || !predicate(&self[start]) {
+                continue;
+            }
+
+            let label = components.len();
+            let mut component = Component::new(start);
+
+            labels[start] = Some(label);
+            frontier.push_back(start);
+
+            while let Some(current) = frontier.pop_front() {
+                component.add(current);
+
+                for neighbor in self.all_neighbors(&current, extended_neighbors) {
+                    if labels[neighbor].is_none() && predicate(&self[neighbor]) {
+                        labels[neighbor] = Some(label);
+                        frontier.push_back(neighbor);
+                    }
+                }
+            }
+
+            components.push(component);
+        }
+
+        Components { labels, components }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+    use crate::p2;
+    use crate::vector2::Point2;
+
+    fn grid() -> Grid<char> {
+        "##..#\n#...#\n..#..\n.#..#\n".parse().unwrap()
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let g = grid();
+
+        let filled = g.flood_fill(p2!(0, 0), |&c| c == '#');
+        assert_eq!(
+            filled
+                .into_iter()
+                .sorted_by_key(|p| (p.y, p.x))
+                .collect_vec(),
+            vec![p2!(0, 0), p2!(1, 0), p2!(0, 1)]
+        );
+
+        assert_eq!(g.flood_fill(p2!(2, 0), |&c| c == '.').len(), 12);
+        assert!(g.flood_fill(p2!(2, 0), |&c| c == '#').is_empty());
+        assert!(g.flood_fill(p2!(9, 9), |_| true).is_empty());
+    }
+
+    #[test]
+    fn test_components() {
+        let g = grid();
+
+        let components = g.components(|&c| c == '#', false);
+        assert_eq!(components.len(), 5);
+        assert_eq!(components.sizes().collect_vec(), vec![3, 2, 1, 1, 1]);
+        assert_eq!(
+            components.components[1],
+            Component {
+                size: 2,
+                min: p2!(4, 0),
+                max: p2!(4, 1),
+            }
+        );
+        assert_eq!(components.label(&p2!(2, 2)), Some(2));
+        assert_eq!(components.label(&p2!(2, 0)), None);
+        assert_eq!(
+            components.points(0).collect_vec(),
+            vec![p2!(0, 0), p2!(1, 0), p2!(0, 1)]
+        );
+
+        let components = g.components(|&c| c == '#', true);
+        assert_eq!(components.sizes().collect_vec(), vec![3, 2, 2, 1]);
+        assert_eq!(
+            components.components[2],
+            Component {
+                size: 2,
+                min: p2!(1, 2),
+                max: p2!(2, 3),
+            }
+        );
+
+        let components = g.components(|_| false, true);
+        assert!(components.is_empty());
+    }
+}
@@ -0,0 +1,235 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use num_traits::ToPrimitive;
+
+use crate::grid::{GPoint, Grid};
+use crate::png;
+use crate::render::{heat_scale, Color};
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum ImageFormat {
+    Pbm,
+    Pgm,
+    Ppm,
+    Png,
+}
+
+impl ImageFormat {
+    #[must_use]
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+
+        match ext.as_str() {
+            "pbm" => Some(ImageFormat::Pbm),
+            "pgm" => Some(ImageFormat::Pgm),
+            "ppm" => Some(ImageFormat::Ppm),
+            "png" => Some(ImageFormat::Png),
+            _ => None,
+        }
+    }
+}
+
+fn luminance(Color(r, g, b): Color) -> u8 {
+    let l = (299 * u32::from(r) + 587 * u32::from(g) + 114 * u32::from(b)) / 1000;
+    u8::try_from(l).unwrap()
+}
+
+impl<T> Grid<T> {
+    // One pixel per cell. PBM and PGM store the luminance of each color, with PBM
+    // treating anything darker than mid-gray as black.
+    #[must_use]
+    pub fn encode_image<F>(&self, format: ImageFormat, palette: F) -> Vec<u8>
+    where
+        F: Fn(&T) -> Color,
+    {
+        let (width, height) = (self.cols(), self.rows());
+        let pixel = |x, y| palette(&self[GPoint::new(x, y)]);
+
+        let header = |magic| format!("{magic}\n{width} {height}\n").into_bytes();
+
+        match format {
+            ImageFormat::Pbm => {
+                let mut out = header("P4");
+
+                for y in 0..height {
+                    for start in (0..width).step_by(8) {
+                        out.push((start..width.min(start + 8)).fold(0, |byte, x| {
+                            byte | u8::from(luminance(pixel(x, y)) < 128) << (7 - x % 8)
+                        }));
+                    }
+                }
+
+                out
+            }
+            ImageFormat::Pgm => {
+                let mut out = header("P5");
+                out.extend(b"255\n");
+                out.extend(self.iter_values().map(|val| luminance(palette(val))));
+                out
+            }
+            ImageFormat::Ppm => {
+                let mut out = header("P6");
+                out.extend(b"255\n");
+
+                for val in self.iter_values() {
+                    let Color(r, g, b) = palette(val);
+                    out.extend([r, g, b]);
+                }
+
+                out
+            }
+            ImageFormat::Png => {
+                let mut out = png::SIGNATURE.to_vec();
+                png::write_chunk(&mut out, *b"IHDR", &png::ihdr(width, height));
+                png::write_chunk(
+                    &mut out,
+                    *b"IDAT",
+                    &png::zlib_stored(&png::rgb_scanlines(width, height, pixel)),
+                );
+                png::write_chunk(&mut out, *b"IEND", &[]);
+                out
+            }
+        }
+    }
+
+    /// Writes the grid as an image, picking the format from the file extension.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidInput` error if the extension isn't one of `pbm`, `pgm`,
+    /// `ppm` or `png`, or any error raised while writing the file.
+    pub fn write_image<P, F>(&self, path: P, palette: F) -> io::Result<()>
+    where
+        P: AsRef<Path>,
+        F: Fn(&T) -> Color,
+    {
+        let path = path.as_ref();
+        let format = ImageFormat::from_path(path).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("unsupported image format: {}", path.display()),
+            )
+        })?;
+
+        fs::write(path, self.encode_image(format, palette))
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: ToPrimitive,
+{
+    // Colors each cell on the `Color::heat` gradient, scaled between the smallest and
+    // largest value in the grid.
+    fn heat_palette(&self) -> impl Fn(&T) -> Color + '_ {
+        let scale = heat_scale(self.iter_values().filter_map(ToPrimitive::to_f64));
+
+        move |val| Color::heat(val.to_f64().map_or(0.0, &scale))
+    }
+
+    #[must_use]
+    pub fn encode_heat_map(&self, format: ImageFormat) -> Vec<u8> {
+        self.encode_image(format, self.heat_palette())
+    }
+
+    /// Writes the grid as a heat map, picking the format from the file extension.
+    ///
+    /// # Errors
+    ///
+    /// See [`Grid::write_image`].
+    pub fn write_heat_map<P>(&self, path: P) -> io::Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        self.write_image(path, self.heat_palette())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    fn palette(&on: &bool) -> Color {
+        if on {
+            Color(0, 0, 0)
+        } else {
+            Color(255, 255, 255)
+        }
+    }
+
+    #[test]
+    fn test_netpbm() {
+        let grid: Grid<bool> = Grid::new(vec![
+            vec![true, false, true, false, false, false, false, false, true],
+            vec![false; 9],
+        ]);
+
+        assert_eq!(
+            grid.encode_image(ImageFormat::Pbm, palette),
+            [b"P4\n9 2\n".as_slice(), &[0b1010_0000, 0b1000_0000, 0, 0]].concat()
+        );
+
+        let grid = Grid::new(vec![vec![true, false]]);
+        assert_eq!(
+            grid.encode_image(ImageFormat::Pgm, palette),
+            [b"P5\n2 1\n255\n".as_slice(), &[0, 255]].concat()
+        );
+        assert_eq!(
+            grid.encode_image(ImageFormat::Ppm, |&on| if on {
+                Color::RED
+            } else {
+                Color::BLUE
+            }),
+            [b"P6\n2 1\n255\n".as_slice(), &[220, 50, 47, 38, 139, 210]].concat()
+        );
+    }
+
+    #[test]
+    fn test_png() {
+        let grid = Grid::new(vec![vec![true, false], vec![false, false]]);
+        let bytes = grid.encode_image(ImageFormat::Png, palette);
+
+        let chunks = png::read_chunks(&bytes);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0].1, png::ihdr(2, 2));
+        assert_eq!(
+            png::unzlib_stored(&chunks[1].1),
+            [0, 0, 0, 0, 255, 255, 255, 0, 255, 255, 255, 255, 255, 255]
+        );
+    }
+
+    #[test]
+    fn test_heat_map() {
+        let grid: Grid<usize> = Grid::new(vec![vec![3, 5, 7]]);
+
+        let mut expected = b"P6\n3 1\n255\n".to_vec();
+        for t in [0.0, 0.5, 1.0] {
+            let Color(r, g, b) = Color::heat(t);
+            expected.extend([r, g, b]);
+        }
+
+        assert_eq!(grid.encode_heat_map(ImageFormat::Ppm), expected);
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(
+            ImageFormat::from_path(Path::new("out/lagoon.PNG")),
+            Some(ImageFormat::Png)
+        );
+        assert_eq!(
+            ImageFormat::from_path(Path::new("map.pgm")),
+            Some(ImageFormat::Pgm)
+        );
+        assert_eq!(ImageFormat::from_path(Path::new("map.gif")), None);
+        assert_eq!(ImageFormat::from_path(Path::new("map")), None);
+
+        let err = Grid::new(vec![vec![true]])
+            .write_image("map.gif", palette)
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+}
@@ -0,0 +1,261 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use hashbrown::HashMap;
+
+use crate::grid::{GPoint, Grid};
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum ParseGridError {
+    InvalidCell {
+        c: char,
+        row: usize,
+        col: usize,
+    },
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseGridError::InvalidCell { c, row, col } => {
+                write!(f, "invalid cell {c:?} at row {row}, column {col}")
+            }
+            ParseGridError::RaggedRow {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {row} has {found} cells, but expected {expected} cells"
+            ),
+        }
+    }
+}
+
+impl Error for ParseGridError {}
+
+pub type Markers = HashMap<char, Vec<GPoint>>;
+
+fn parse_cells<T, F>(s: &str, mut convert: F) -> Result<Grid<T>, ParseGridError>
+where
+    F: FnMut(GPoint, char) -> Option<T>,
+{
+    let mut elems: Vec<Vec<T>> = vec![];
+
+    for (row, line) in s.lines().enumerate() {
+        let cells = line
+            .chars()
+            .enumerate()
+            .map(|(col, c)| {
+                convert(GPoint::new(col, row), c).ok_or(ParseGridError::InvalidCell { c, row, col })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if let Some(first) = elems.first() {
+            if first.len() != cells.len() {
+                return Err(ParseGridError::RaggedRow {
+                    row,
+                    expected: first.len(),
+                    found: cells.len(),
+                });
+            }
+        }
+
+        elems.push(cells);
+    }
+
+    Ok(Grid::new(elems))
+}
+
+impl<T> Grid<T>
+where
+    T: TryFrom<char>,
+{
+    /// Parses `s`, recording the coordinates of every marker character and storing
+    /// its substitute in the grid instead, e.g. `('S', 'a')` for a start position.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a (substituted) cell can't be converted or the rows are ragged.
+    pub fn parse_with_markers(
+        s: &str,
+        markers: &[(char, char)],
+    ) -> Result<(Self, Markers), ParseGridError> {
+        let mut found = Markers::new();
+
+        let grid = parse_cells(s, |p, c| {
+            let substitute = markers.iter().find(|(marker, _)| *marker == c).map_or(
+                c,
+                |&(marker, substitute)| {
+                    found.entry(marker).or_default().push(p);
+                    substitute
+                },
+            );
+
+            T::try_from(substitute).ok()
+        })?;
+
+        Ok((grid, found))
+    }
+}
+
+impl<T> FromStr for Grid<T>
+where
+    T: TryFrom<char>,
+{
+    type Err = ParseGridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_cells(s, |_, c| T::try_from(c).ok())
+    }
+}
+
+impl<T> TryFrom<&str> for Grid<T>
+where
+    T: TryFrom<char>,
+{
+    type Error = ParseGridError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::p2;
+    use crate::vector2::Point2;
+
+    #[derive(Debug, Eq, PartialEq)]
+    enum Cell {
+        Wall,
+        Open,
+    }
+
+    impl TryFrom<char> for Cell {
+        type Error = ();
+
+        fn try_from(c: char) -> Result<Self, Self::Error> {
+            match c {
+                '#' => Ok(Cell::Wall),
+                '.' => Ok(Cell::Open),
+                _ => Err(()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_cell() {
+        let result: Result<Grid<Cell>, _> = "#.\n.x\n".parse();
+        assert_eq!(
+            result,
+            Err(ParseGridError::InvalidCell {
+                c: 'x',
+                row: 1,
+                col: 1
+            })
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "invalid cell 'x' at row 1, column 1"
+        );
+
+        let result = Grid::<u8>::try_from("ab\ncλ\n");
+        assert_eq!(
+            result,
+            Err(ParseGridError::InvalidCell {
+                c: 'λ',
+                row: 1,
+                col: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_ragged_rows() {
+        let result: Result<Grid<Cell>, _> = "#.#\n.#\n###\n".parse();
+        assert_eq!(
+            result,
+            Err(ParseGridError::RaggedRow {
+                row: 1,
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "row 1 has 2 cells, but expected 3 cells"
+        );
+    }
+
+    #[test]
+    fn test_empty() {
+        let grid: Grid<Cell> = "".parse().unwrap();
+        assert_eq!(grid.rows(), 0);
+        assert_eq!(grid.cols(), 0);
+    }
+
+    #[test]
+    fn test_parse_with_markers() {
+        let (grid, markers) =
+            Grid::<Cell>::parse_with_markers("S.#\n#.E\nS..\n", &[('S', '.'), ('E', '.')]).unwrap();
+
+        assert_eq!(grid, "..#\n#..\n...\n".parse().unwrap());
+        assert_eq!(markers[&'S'], vec![p2!(0, 0), p2!(0, 2)]);
+        assert_eq!(markers[&'E'], vec![p2!(2, 1)]);
+        assert!(!markers.contains_key(&'X'));
+
+        let result = Grid::<Cell>::parse_with_markers("S.\n.E\n", &[('S', '.')]);
+        assert_eq!(
+            result.map(|(grid, _)| grid),
+            Err(ParseGridError::InvalidCell {
+                c: 'E',
+                row: 1,
+                col: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_derive_grid_cell() {
+        use crate::grid::GridCell;
+
+        #[derive(GridCell, Debug, Eq, PartialEq, Copy, Clone)]
+        enum Tile {
+            #[cell('.')]
+            Empty,
+            #[cell('#')]
+            Rock,
+            #[cell('\\')]
+            Mirror,
+        }
+
+        assert_eq!(Tile::try_from('#'), Ok(Tile::Rock));
+        assert_eq!(Tile::try_from('x'), Err('x'));
+        assert_eq!(char::from(Tile::Mirror), '\\');
+        assert_eq!(char::from(&Tile::Empty), '.');
+        assert_eq!(Tile::Rock.to_string(), "#");
+
+        let s = "#.\\\n.#.\n";
+        let grid: Grid<Tile> = s.parse().unwrap();
+        assert_eq!(grid[GPoint::new(2, 0)], Tile::Mirror);
+        assert_eq!(grid.to_string(), s);
+
+        let result: Result<Grid<Tile>, _> = ".#\n#O\n".parse();
+        assert_eq!(
+            result,
+            Err(ParseGridError::InvalidCell {
+                c: 'O',
+                row: 1,
+                col: 1
+            })
+        );
+    }
+}
@@ -0,0 +1,99 @@
+use crate::grid::{GPoint, Grid, Symmetry};
+
+impl<T> Grid<T>
+where
+    T: PartialEq,
+{
+    fn matches_at(&self, pattern: &Grid<T>, wildcard: &T, offset: GPoint) -> bool {
+        pattern.iter_points_values().all(|(p, val)| {
+            val == wildcard || self[GPoint::new(offset.x + p.x, offset.y + p.y)] == *val
+        })
+    }
+
+    // Returns the top-left offset of every place `pattern` occurs, where cells equal to
+    // `wildcard` in the pattern match anything.
+    #[must_use]
+    pub fn find_pattern(&self, pattern: &Grid<T>, wildcard: &T) -> Vec<GPoint> {
+        if pattern.rows() > self.rows() || pattern.cols() > self.cols() {
+            return vec![];
+        }
+
+        (0..=self.rows() - pattern.rows())
+            .flat_map(|y| (0..=self.cols() - pattern.cols()).map(move |x| GPoint::new(x, y)))
+            .filter(|&offset| self.matches_at(pattern, wildcard, offset))
+            .collect()
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: PartialEq + Clone,
+{
+    // Like `find_pattern`, but also tries `pattern` under each of the 8 symmetries.
+    // Offsets refer to the top-left of the transformed pattern.
+    #[must_use]
+    pub fn find_pattern_oriented(
+        &self,
+        pattern: &Grid<T>,
+        wildcard: &T,
+    ) -> Vec<(Symmetry, GPoint)> {
+        Symmetry::ALL
+            .into_iter()
+            .flat_map(|symmetry| {
+                self.find_pattern(&pattern.transformed(symmetry), wildcard)
+                    .into_iter()
+                    .map(move |offset| (symmetry, offset))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::p2;
+    use crate::vector2::Point2;
+
+    #[test]
+    fn test_find_pattern() {
+        let g: Grid<char> = "#.#.#\n.#.#.\n#.#..\n".parse().unwrap();
+        let pattern: Grid<char> = "#?#\n?#?\n".parse().unwrap();
+
+        assert_eq!(
+            g.find_pattern(&pattern, &'?'),
+            vec![p2!(0, 0), p2!(2, 0), p2!(1, 1)]
+        );
+
+        let pattern: Grid<char> = ".#.\n".parse().unwrap();
+        assert_eq!(
+            g.find_pattern(&pattern, &'?'),
+            vec![p2!(1, 0), p2!(0, 1), p2!(2, 1), p2!(1, 2)]
+        );
+
+        let too_big: Grid<char> = "??????\n".parse().unwrap();
+        assert!(g.find_pattern(&too_big, &'?').is_empty());
+    }
+
+    #[test]
+    fn test_find_pattern_oriented() {
+        let g: Grid<char> = "....\n.#..\n.##.\n....\n".parse().unwrap();
+        let pattern: Grid<char> = "#?\n##\n".parse().unwrap();
+
+        assert_eq!(
+            g.find_pattern_oriented(&pattern, &'?'),
+            vec![
+                (Symmetry::Identity, p2!(1, 1)),
+                (Symmetry::AntiTranspose, p2!(1, 1))
+            ]
+        );
+
+        let pattern: Grid<char> = "?#\n##\n".parse().unwrap();
+        assert_eq!(
+            g.find_pattern_oriented(&pattern, &'?'),
+            vec![
+                (Symmetry::Rotate90, p2!(1, 1)),
+                (Symmetry::FlipHorizontal, p2!(1, 1))
+            ]
+        );
+    }
+}
@@ -0,0 +1,148 @@
+use std::iter;
+
+use crate::grid::{GPoint, Grid};
+use crate::vector2::Direction;
+use crate::vector2::Direction::{Down, DownLeft, DownRight, Right, UpRight};
+
+impl<T> Grid<T> {
+    pub fn ray(
+        &self,
+        from: GPoint,
+        direction: Direction,
+    ) -> impl Iterator<Item = (GPoint, &T)> + '_ {
+        iter::successors(self.get_neighbor(&from, direction), move |p| {
+            self.get_neighbor(p, direction)
+        })
+        .map(move |p| (p, &self[p]))
+    }
+
+    pub fn first_in_direction<F>(
+        &self,
+        from: GPoint,
+        direction: Direction,
+        predicate: F,
+    ) -> Option<(GPoint, &T)>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.ray(from, direction).find(|(_, val)| predicate(val))
+    }
+
+    // For every cell, the number of steps in `direction` to the nearest blocking cell,
+    // or `None` if the ray reaches the edge first.
+    pub fn distances_to_blocking<F>(
+        &self,
+        direction: Direction,
+        is_blocking: F,
+    ) -> Grid<Option<usize>>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let mut distances = Grid::new(vec![vec![None; self.cols()]; self.rows()]);
+
+        // visit cells so that the neighbor in `direction` is always computed first
+        let rows_reversed = matches!(direction, Down | DownLeft | DownRight);
+        let cols_reversed = matches!(direction, Right | UpRight | DownRight);
+
+        let mut ys = (0..self.rows()).collect::<Vec<_>>();
+        let mut xs = (0..self.cols()).collect::<Vec<_>>();
+
+        if rows_reversed {
+            ys.reverse();
+        }
+        if cols_reversed {
+            xs.reverse();
+        }
+
+        for &y in &ys {
+            for &x in &xs {
+                let p = GPoint::new(x, y);
+
+                distances[p] = self.get_neighbor(&p, direction).and_then(|neighbor| {
+                    if is_blocking(&self[neighbor]) {
+                        Some(1)
+                    } else {
+                        distances[neighbor].map(|d| d + 1)
+                    }
+                });
+            }
+        }
+
+        distances
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+    use crate::p2;
+    use crate::vector2::Direction::{Left, Up};
+    use crate::vector2::{Point2, EIGHT_NEIGHBORS};
+
+    fn grid() -> Grid<char> {
+        "#..#\n.#..\n...#\n".parse().unwrap()
+    }
+
+    #[test]
+    fn test_ray() {
+        let g = grid();
+
+        assert_eq!(
+            g.ray(p2!(0, 1), Right).collect_vec(),
+            vec![(p2!(1, 1), &'#'), (p2!(2, 1), &'.'), (p2!(3, 1), &'.')]
+        );
+        assert_eq!(
+            g.ray(p2!(0, 2), UpRight).collect_vec(),
+            vec![(p2!(1, 1), &'#'), (p2!(2, 0), &'.')]
+        );
+        assert_eq!(g.ray(p2!(0, 1), Left).count(), 0);
+        assert_eq!(g.ray(p2!(2, 0), Up).count(), 0);
+    }
+
+    #[test]
+    fn test_first_in_direction() {
+        let g = grid();
+
+        assert_eq!(
+            g.first_in_direction(p2!(3, 1), Left, |&c| c == '#'),
+            Some((p2!(1, 1), &'#'))
+        );
+        assert_eq!(
+            g.first_in_direction(p2!(3, 0), DownLeft, |&c| c == '#'),
+            None
+        );
+        assert_eq!(
+            g.first_in_direction(p2!(2, 2), UpRight, |&c| c == '#'),
+            None
+        );
+    }
+
+    #[test]
+    fn test_distances_to_blocking() {
+        let g = grid();
+
+        for direction in EIGHT_NEIGHBORS {
+            let distances = g.distances_to_blocking(direction, |&c| c == '#');
+
+            for p in g.iter_points() {
+                let expected = g
+                    .ray(p, direction)
+                    .position(|(_, &c)| c == '#')
+                    .map(|n| n + 1);
+
+                assert_eq!(distances[p], expected, "{p} {direction:?}");
+            }
+        }
+
+        assert_eq!(
+            g.distances_to_blocking(Right, |&c| c == '#'),
+            Grid::new(vec![
+                vec![Some(3), Some(2), Some(1), None],
+                vec![Some(1), None, None, None],
+                vec![Some(3), Some(2), Some(1), None],
+            ])
+        );
+    }
+}
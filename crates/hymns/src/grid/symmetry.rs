@@ -0,0 +1,195 @@
+use crate::grid::{GPoint, Grid, Symmetry};
+
+// Returns every axis `m` in `1..len` where reflecting lines `m - 1, m - 2, ...` onto
+// `m, m + 1, ...` leaves at most `tolerance` cells mismatched, with the count of
+// mismatched cells for each.
+fn find_mirrors<F>(len: usize, tolerance: usize, mismatches: F) -> Vec<(usize, usize)>
+where
+    F: Fn(usize, usize) -> usize,
+{
+    (1..len)
+        .filter_map(|m| {
+            let mut total = 0;
+
+            for (a, b) in (0..m).rev().zip(m..len) {
+                total += mismatches(a, b);
+
+                if total > tolerance {
+                    return None;
+                }
+            }
+
+            Some((m, total))
+        })
+        .collect()
+}
+
+fn exactly(mirrors: Vec<(usize, usize)>, mismatches: usize) -> Vec<usize> {
+    mirrors
+        .into_iter()
+        .filter(|&(_, total)| total == mismatches)
+        .map(|(m, _)| m)
+        .collect()
+}
+
+impl<T> Grid<T>
+where
+    T: PartialEq,
+{
+    fn row_mirrors(&self, tolerance: usize) -> Vec<(usize, usize)> {
+        find_mirrors(self.rows(), tolerance, |a, b| {
+            (0..self.cols())
+                .filter(|&x| self[GPoint::new(x, a)] != self[GPoint::new(x, b)])
+                .count()
+        })
+    }
+
+    fn col_mirrors(&self, tolerance: usize) -> Vec<(usize, usize)> {
+        find_mirrors(self.cols(), tolerance, |a, b| {
+            (0..self.rows())
+                .filter(|&y| self[GPoint::new(a, y)] != self[GPoint::new(b, y)])
+                .count()
+        })
+    }
+
+    // Mirrors between rows that hold with up to `tolerance` mismatched cells: `m` is
+    // the number of rows above the axis.
+    #[must_use]
+    pub fn horizontal_mirrors(&self, tolerance: usize) -> Vec<usize> {
+        self.row_mirrors(tolerance)
+            .into_iter()
+            .map(|(m, _)| m)
+            .collect()
+    }
+
+    // Mirrors between columns that hold with up to `tolerance` mismatched cells: `m` is
+    // the number of columns left of the axis.
+    #[must_use]
+    pub fn vertical_mirrors(&self, tolerance: usize) -> Vec<usize> {
+        self.col_mirrors(tolerance)
+            .into_iter()
+            .map(|(m, _)| m)
+            .collect()
+    }
+
+    // Like `horizontal_mirrors`, but only the axes with exactly `mismatches` mismatched
+    // cells, e.g. 1 to find the mirror a single smudge hides.
+    #[must_use]
+    pub fn horizontal_mirrors_exact(&self, mismatches: usize) -> Vec<usize> {
+        exactly(self.row_mirrors(mismatches), mismatches)
+    }
+
+    #[must_use]
+    pub fn vertical_mirrors_exact(&self, mismatches: usize) -> Vec<usize> {
+        exactly(self.col_mirrors(mismatches), mismatches)
+    }
+
+    #[must_use]
+    pub fn is_symmetric_under(&self, symmetry: Symmetry) -> bool {
+        if symmetry.swaps_axes() && self.rows() != self.cols() {
+            return false;
+        }
+
+        self.iter_points_values()
+            .all(|(p, val)| self[symmetry.source(p, self.rows(), self.cols())] == *val)
+    }
+
+    // Every symmetry that maps the grid onto itself, always including `Identity`.
+    #[must_use]
+    pub fn symmetries(&self) -> Vec<Symmetry> {
+        Symmetry::ALL
+            .into_iter()
+            .filter(|&symmetry| self.is_symmetric_under(symmetry))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mirrors() {
+        let g: Grid<char> = concat!(
+            "#.##..##.\n",
+            "..#.##.#.\n",
+            "##......#\n",
+            "##......#\n",
+            "..#.##.#.\n",
+            "..##..##.\n",
+            "#.#.##.#.\n",
+        )
+        .parse()
+        .unwrap();
+
+        assert_eq!(g.vertical_mirrors(0), vec![5]);
+        assert_eq!(g.horizontal_mirrors(0), Vec::<usize>::new());
+        assert_eq!(g.horizontal_mirrors_exact(1), vec![3]);
+
+        let g: Grid<char> = concat!(
+            "#...##..#\n",
+            "#....#..#\n",
+            "..##..###\n",
+            "#####.##.\n",
+            "#####.##.\n",
+            "..##..###\n",
+            "#....#..#\n",
+        )
+        .parse()
+        .unwrap();
+
+        assert_eq!(g.horizontal_mirrors(0), vec![4]);
+        assert_eq!(g.horizontal_mirrors_exact(1), vec![1]);
+        assert_eq!(g.vertical_mirrors(0), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_mirrors_tolerance() {
+        let g: Grid<char> = concat!(
+            "#...##..#\n",
+            "#....#..#\n",
+            "..##..###\n",
+            "#####.##.\n",
+            "#####.##.\n",
+            "..##..###\n",
+            "#....#..#\n",
+        )
+        .parse()
+        .unwrap();
+
+        // a tolerance keeps the perfect mirror, exact matching only the smudged one
+        assert_eq!(g.horizontal_mirrors(1), vec![1, 4]);
+        assert_eq!(g.horizontal_mirrors_exact(1), vec![1]);
+        assert_eq!(g.horizontal_mirrors_exact(0), g.horizontal_mirrors(0));
+
+        assert_eq!(g.vertical_mirrors(3), vec![1, 3, 7]);
+        assert_eq!(g.vertical_mirrors_exact(3), vec![1, 3]);
+    }
+
+    #[test]
+    fn test_symmetries() {
+        let g: Grid<char> = "#.#\n...\n#.#\n".parse().unwrap();
+        assert_eq!(g.symmetries(), Symmetry::ALL.to_vec());
+
+        let g: Grid<char> = "#..\n.#.\n..#\n".parse().unwrap();
+        assert_eq!(
+            g.symmetries(),
+            vec![
+                Symmetry::Identity,
+                Symmetry::Rotate180,
+                Symmetry::Transpose,
+                Symmetry::AntiTranspose
+            ]
+        );
+
+        let g: Grid<char> = "#.\n.#\n#.\n".parse().unwrap();
+        assert_eq!(
+            g.symmetries(),
+            vec![Symmetry::Identity, Symmetry::FlipVertical]
+        );
+        assert!(!g.is_symmetric_under(Symmetry::Transpose));
+
+        let g: Grid<char> = "##.\n..#\n".parse().unwrap();
+        assert_eq!(g.symmetries(), vec![Symmetry::Identity]);
+    }
+}
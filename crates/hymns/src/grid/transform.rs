@@ -0,0 +1,113 @@
+use crate::grid::{GPoint, Grid};
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Symmetry {
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    FlipHorizontal,
+    FlipVertical,
+    Transpose,
+    AntiTranspose,
+}
+
+impl Symmetry {
+    pub const ALL: [Symmetry; 8] = [
+        Symmetry::Identity,
+        Symmetry::Rotate90,
+        Symmetry::Rotate180,
+        Symmetry::Rotate270,
+        Symmetry::FlipHorizontal,
+        Symmetry::FlipVertical,
+        Symmetry::Transpose,
+        Symmetry::AntiTranspose,
+    ];
+
+    #[must_use]
+    pub fn swaps_axes(self) -> bool {
+        matches!(
+            self,
+            Symmetry::Rotate90
+                | Symmetry::Rotate270
+                | Symmetry::Transpose
+                | Symmetry::AntiTranspose
+        )
+    }
+
+    // Maps a point of the transformed grid back to the original grid, which has
+    // `rows` rows and `cols` columns.
+    pub(super) fn source(self, p: GPoint, rows: usize, cols: usize) -> GPoint {
+        let GPoint { x, y } = p;
+
+        match self {
+            Symmetry::Identity => GPoint::new(x, y),
+            Symmetry::Rotate90 => GPoint::new(y, rows - 1 - x),
+            Symmetry::Rotate180 => GPoint::new(cols - 1 - x, rows - 1 - y),
+            Symmetry::Rotate270 => GPoint::new(cols - 1 - y, x),
+            Symmetry::FlipHorizontal => GPoint::new(cols - 1 - x, y),
+            Symmetry::FlipVertical => GPoint::new(x, rows - 1 - y),
+            Symmetry::Transpose => GPoint::new(y, x),
+            Symmetry::AntiTranspose => GPoint::new(cols - 1 - y, rows - 1 - x),
+        }
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    // Rotations are clockwise.
+    #[must_use]
+    pub fn transformed(&self, symmetry: Symmetry) -> Grid<T> {
+        let (rows, cols) = if symmetry.swaps_axes() {
+            (self.cols(), self.rows())
+        } else {
+            (self.rows(), self.cols())
+        };
+
+        Grid::new(
+            (0..rows)
+                .map(|y| {
+                    (0..cols)
+                        .map(|x| {
+                            let src = symmetry.source(GPoint::new(x, y), self.rows(), self.cols());
+                            self[src].clone()
+                        })
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transformed() {
+        let g: Grid<char> = "abc\ndef\n".parse().unwrap();
+
+        let expected = [
+            (Symmetry::Identity, "abc\ndef\n"),
+            (Symmetry::Rotate90, "da\neb\nfc\n"),
+            (Symmetry::Rotate180, "fed\ncba\n"),
+            (Symmetry::Rotate270, "cf\nbe\nad\n"),
+            (Symmetry::FlipHorizontal, "cba\nfed\n"),
+            (Symmetry::FlipVertical, "def\nabc\n"),
+            (Symmetry::Transpose, "ad\nbe\ncf\n"),
+            (Symmetry::AntiTranspose, "fc\neb\nda\n"),
+        ];
+
+        for (symmetry, s) in expected {
+            assert_eq!(g.transformed(symmetry).to_string(), s, "{symmetry:?}");
+        }
+
+        assert_eq!(
+            g.transformed(Symmetry::Rotate90)
+                .transformed(Symmetry::Rotate270),
+            g
+        );
+    }
+}
@@ -0,0 +1,378 @@
+// Axial coordinates for hexagonal grids, see https://www.redblobgames.com/grids/hexagons/
+
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign, Mul, Sub};
+use std::str::FromStr;
+
+#[derive(Debug, Eq, PartialEq, Hash, Default, Copy, Clone, Ord, PartialOrd)]
+pub struct Hex {
+    pub q: i64,
+    pub r: i64,
+}
+
+// Directions between pointy-top hexes, which share edges to the east and west.
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+pub enum HexDirection {
+    East,
+    SouthEast,
+    SouthWest,
+    West,
+    NorthWest,
+    NorthEast,
+}
+
+// Directions between flat-top hexes, which share edges to the north and south.
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+pub enum FlatHexDirection {
+    North,
+    NorthEast,
+    SouthEast,
+    South,
+    SouthWest,
+    NorthWest,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ParseHexDirectionError(pub String);
+
+impl Display for ParseHexDirectionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid hex direction {:?}", self.0)
+    }
+}
+
+impl Error for ParseHexDirectionError {}
+
+// Splits a path such as `nwwswee` or `ne,ne,s` into directions; `n` and `s` combine with
+// a following `e` or `w`.
+fn parse_path<D>(s: &str) -> Result<Vec<D>, ParseHexDirectionError>
+where
+    D: FromStr<Err = ParseHexDirectionError>,
+{
+    let mut directions = vec![];
+    let mut rest = s;
+
+    while let Some(c) = rest.chars().next() {
+        if c == ',' || c.is_whitespace() {
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+
+        let len = if matches!(c, 'n' | 's') && matches!(rest[1..].chars().next(), Some('e' | 'w')) {
+            2
+        } else {
+            c.len_utf8()
+        };
+
+        directions.push(rest[..len].parse()?);
+        rest = &rest[len..];
+    }
+
+    Ok(directions)
+}
+
+impl HexDirection {
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::East,
+        HexDirection::SouthEast,
+        HexDirection::SouthWest,
+        HexDirection::West,
+        HexDirection::NorthWest,
+        HexDirection::NorthEast,
+    ];
+
+    #[must_use]
+    pub const fn offset(self) -> Hex {
+        match self {
+            HexDirection::East => Hex::new(1, 0),
+            HexDirection::SouthEast => Hex::new(0, 1),
+            HexDirection::SouthWest => Hex::new(-1, 1),
+            HexDirection::West => Hex::new(-1, 0),
+            HexDirection::NorthWest => Hex::new(0, -1),
+            HexDirection::NorthEast => Hex::new(1, -1),
+        }
+    }
+
+    /// Parses a run of directions such as `esenee`, optionally separated by commas.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error on the first token that isn't a pointy-top direction.
+    pub fn parse_path(s: &str) -> Result<Vec<Self>, ParseHexDirectionError> {
+        parse_path(s)
+    }
+}
+
+impl FromStr for HexDirection {
+    type Err = ParseHexDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "e" => Ok(HexDirection::East),
+            "se" => Ok(HexDirection::SouthEast),
+            "sw" => Ok(HexDirection::SouthWest),
+            "w" => Ok(HexDirection::West),
+            "nw" => Ok(HexDirection::NorthWest),
+            "ne" => Ok(HexDirection::NorthEast),
+            _ => Err(ParseHexDirectionError(s.to_string())),
+        }
+    }
+}
+
+impl FlatHexDirection {
+    pub const ALL: [FlatHexDirection; 6] = [
+        FlatHexDirection::North,
+        FlatHexDirection::NorthEast,
+        FlatHexDirection::SouthEast,
+        FlatHexDirection::South,
+        FlatHexDirection::SouthWest,
+        FlatHexDirection::NorthWest,
+    ];
+
+    #[must_use]
+    pub const fn offset(self) -> Hex {
+        match self {
+            FlatHexDirection::North => Hex::new(0, -1),
+            FlatHexDirection::NorthEast => Hex::new(1, -1),
+            FlatHexDirection::SouthEast => Hex::new(1, 0),
+            FlatHexDirection::South => Hex::new(0, 1),
+            FlatHexDirection::SouthWest => Hex::new(-1, 1),
+            FlatHexDirection::NorthWest => Hex::new(-1, 0),
+        }
+    }
+
+    /// Parses a run of directions such as `ne,ne,s,s`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error on the first token that isn't a flat-top direction.
+    pub fn parse_path(s: &str) -> Result<Vec<Self>, ParseHexDirectionError> {
+        parse_path(s)
+    }
+}
+
+impl FromStr for FlatHexDirection {
+    type Err = ParseHexDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "n" => Ok(FlatHexDirection::North),
+            "ne" => Ok(FlatHexDirection::NorthEast),
+            "se" => Ok(FlatHexDirection::SouthEast),
+            "s" => Ok(FlatHexDirection::South),
+            "sw" => Ok(FlatHexDirection::SouthWest),
+            "nw" => Ok(FlatHexDirection::NorthWest),
+            _ => Err(ParseHexDirectionError(s.to_string())),
+        }
+    }
+}
+
+impl From<HexDirection> for Hex {
+    fn from(direction: HexDirection) -> Self {
+        direction.offset()
+    }
+}
+
+impl From<FlatHexDirection> for Hex {
+    fn from(direction: FlatHexDirection) -> Self {
+        direction.offset()
+    }
+}
+
+impl Hex {
+    #[must_use]
+    pub const fn new(q: i64, r: i64) -> Self {
+        Self { q, r }
+    }
+
+    #[must_use]
+    pub fn from_cube(q: i64, r: i64, s: i64) -> Self {
+        debug_assert_eq!(q + r + s, 0);
+        Self { q, r }
+    }
+
+    // The third cube coordinate, so that `q + r + s == 0`.
+    #[must_use]
+    pub const fn s(self) -> i64 {
+        -self.q - self.r
+    }
+
+    #[must_use]
+    pub fn neighbor(self, direction: impl Into<Hex>) -> Self {
+        self + direction.into()
+    }
+
+    // Takes `&self` so it can be passed straight to `SparseAutomaton::new`.
+    #[must_use]
+    pub fn neighbors(&self) -> [Hex; 6] {
+        HexDirection::ALL.map(|direction| *self + direction.offset())
+    }
+
+    #[must_use]
+    pub fn distance(self, other: Hex) -> u64 {
+        let d = self - other;
+        d.q.unsigned_abs()
+            .max(d.r.unsigned_abs())
+            .max(d.s().unsigned_abs())
+    }
+
+    // Every hex exactly `radius` steps away, walking counterclockwise from the
+    // south-west corner.
+    #[must_use]
+    pub fn ring(self, radius: u32) -> Vec<Hex> {
+        if radius == 0 {
+            return vec![self];
+        }
+
+        let mut hex = self + HexDirection::SouthWest.offset() * i64::from(radius);
+        let mut ring = Vec::with_capacity(6 * radius as usize);
+
+        for direction in [
+            HexDirection::East,
+            HexDirection::NorthEast,
+            HexDirection::NorthWest,
+            HexDirection::West,
+            HexDirection::SouthWest,
+            HexDirection::SouthEast,
+        ] {
+            for _ in 0..radius {
+                ring.push(hex);
+                hex = hex.neighbor(direction);
+            }
+        }
+
+        ring
+    }
+
+    // Every hex at most `radius` steps away.
+    #[must_use]
+    pub fn range(self, radius: u32) -> Vec<Hex> {
+        let n = i64::from(radius);
+
+        (-n..=n)
+            .flat_map(|dq| {
+                ((-n).max(-dq - n)..=n.min(-dq + n)).map(move |dr| self + Hex::new(dq, dr))
+            })
+            .collect()
+    }
+}
+
+impl Display for Hex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.q, self.r)
+    }
+}
+
+impl Add for Hex {
+    type Output = Hex;
+
+    fn add(self, rhs: Hex) -> Self::Output {
+        Hex::new(self.q + rhs.q, self.r + rhs.r)
+    }
+}
+
+impl AddAssign for Hex {
+    fn add_assign(&mut self, rhs: Hex) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Hex {
+    type Output = Hex;
+
+    fn sub(self, rhs: Hex) -> Self::Output {
+        Hex::new(self.q - rhs.q, self.r - rhs.r)
+    }
+}
+
+impl Mul<i64> for Hex {
+    type Output = Hex;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Hex::new(self.q * rhs, self.r * rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_path() {
+        use HexDirection::{East, NorthEast, NorthWest, SouthEast, SouthWest, West};
+
+        assert_eq!(
+            HexDirection::parse_path("esenwswne"),
+            Ok(vec![East, SouthEast, NorthWest, SouthWest, NorthEast])
+        );
+        assert_eq!(HexDirection::parse_path("w, w"), Ok(vec![West, West]));
+        assert_eq!(
+            HexDirection::parse_path("nen"),
+            Err(ParseHexDirectionError("n".to_string()))
+        );
+
+        assert_eq!(
+            FlatHexDirection::parse_path("ne,ne,s,s"),
+            Ok(vec![
+                FlatHexDirection::NorthEast,
+                FlatHexDirection::NorthEast,
+                FlatHexDirection::South,
+                FlatHexDirection::South
+            ])
+        );
+        assert!(FlatHexDirection::parse_path("n,e").is_err());
+    }
+
+    #[test]
+    fn test_walk_and_distance() {
+        let walk = |path: &str| {
+            HexDirection::parse_path(path)
+                .unwrap()
+                .into_iter()
+                .fold(Hex::default(), Hex::neighbor)
+        };
+
+        assert_eq!(walk("nwwswee"), Hex::default());
+        assert_eq!(walk("esew"), Hex::new(0, 1));
+
+        let walk = |path: &str| {
+            FlatHexDirection::parse_path(path)
+                .unwrap()
+                .into_iter()
+                .fold(Hex::default(), Hex::neighbor)
+        };
+
+        assert_eq!(walk("ne,ne,ne").distance(Hex::default()), 3);
+        assert_eq!(walk("ne,ne,sw,sw").distance(Hex::default()), 0);
+        assert_eq!(walk("ne,ne,s,s").distance(Hex::default()), 2);
+        assert_eq!(walk("se,sw,se,sw,sw").distance(Hex::default()), 3);
+
+        let h = Hex::from_cube(2, -3, 1);
+        assert_eq!(h.s(), 1);
+        assert_eq!(h.distance(Hex::new(-1, 0)), 3);
+    }
+
+    #[test]
+    fn test_ring_and_range() {
+        let center = Hex::new(3, -1);
+
+        assert_eq!(center.ring(0), vec![center]);
+
+        for radius in 1..4 {
+            let ring = center.ring(radius);
+            assert_eq!(ring.len(), 6 * radius as usize);
+            assert!(ring.iter().all(|h| h.distance(center) == u64::from(radius)));
+        }
+
+        let mut neighbors = center.neighbors().to_vec();
+        let mut ring = center.ring(1);
+        neighbors.sort_unstable();
+        ring.sort_unstable();
+        assert_eq!(neighbors, ring);
+
+        let range = center.range(2);
+        assert_eq!(range.len(), 19);
+        assert!(range.iter().all(|h| h.distance(center) <= 2));
+    }
+}
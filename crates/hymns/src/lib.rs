@@ -1,13 +1,22 @@
 pub mod all_equal;
+pub mod animation;
+pub mod automaton;
+pub mod bit_grid;
 pub mod default_map;
 pub mod formatting;
 pub mod geom;
+pub mod graph;
 pub mod grid;
+pub mod hex;
 pub mod input;
 pub mod invert_map;
 pub mod math;
 pub mod more_itertools;
+pub mod ocr;
 pub mod pathfinding;
+mod png;
+pub mod render;
 pub mod runner;
+pub mod search;
 pub mod vector2;
 pub mod vector3;
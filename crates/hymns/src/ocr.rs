@@ -0,0 +1,442 @@
+// Recognizes the block letters drawn by "render the screen" puzzles.
+
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use crate::grid::{GPoint, Grid};
+
+const SMALL_FONT: &[(char, [&str; 6])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+const LARGE_FONT: &[(char, [&str; 10])] = &[
+    (
+        'A',
+        [
+            "..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'B',
+        [
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#",
+            "#....#", "#####.",
+        ],
+    ),
+    (
+        'C',
+        [
+            ".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+            "#....#", ".####.",
+        ],
+    ),
+    (
+        'E',
+        [
+            "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+            "#.....", "######",
+        ],
+    ),
+    (
+        'F',
+        [
+            "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+            "#.....", "#.....",
+        ],
+    ),
+    (
+        'G',
+        [
+            ".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#",
+            "#...##", ".###.#",
+        ],
+    ),
+    (
+        'H',
+        [
+            "#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'J',
+        [
+            "...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.",
+            "#...#.", ".###..",
+        ],
+    ),
+    (
+        'K',
+        [
+            "#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..",
+            "#...#.", "#....#",
+        ],
+    ),
+    (
+        'L',
+        [
+            "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+            "#.....", "######",
+        ],
+    ),
+    (
+        'N',
+        [
+            "#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##",
+            "#...##", "#....#",
+        ],
+    ),
+    (
+        'P',
+        [
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....",
+            "#.....", "#.....",
+        ],
+    ),
+    (
+        'R',
+        [
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'X',
+        [
+            "#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'Z',
+        [
+            "######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....",
+            "#.....", "######",
+        ],
+    ),
+];
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum OcrError {
+    Empty,
+    UnsupportedHeight(usize),
+    UnknownGlyph { index: usize, glyph: String },
+}
+
+impl Display for OcrError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OcrError::Empty => write!(f, "no lit cells to recognize"),
+            OcrError::UnsupportedHeight(height) => {
+                write!(f, "letters are {height} cells tall, expected 6 or 10")
+            }
+            OcrError::UnknownGlyph { index, glyph } => {
+                write!(f, "unknown glyph at position {index}:\n{glyph}")
+            }
+        }
+    }
+}
+
+impl Error for OcrError {}
+
+type Bitmap = Vec<Vec<bool>>;
+
+fn column_is_blank(bitmap: &[Vec<bool>], x: usize) -> bool {
+    bitmap.iter().all(|row| !row[x])
+}
+
+fn columns(bitmap: &[Vec<bool>], start: usize, end: usize) -> Bitmap {
+    bitmap.iter().map(|row| row[start..end].to_vec()).collect()
+}
+
+fn trim_columns(bitmap: &[Vec<bool>]) -> Bitmap {
+    let width = bitmap.first().map_or(0, Vec::len);
+    let start = (0..width)
+        .find(|&x| !column_is_blank(bitmap, x))
+        .unwrap_or(width);
+    let end = (start..width)
+        .rev()
+        .find(|&x| !column_is_blank(bitmap, x))
+        .map_or(start, |x| x + 1);
+
+    columns(bitmap, start, end)
+}
+
+fn parse_glyph(rows: &[&str]) -> Bitmap {
+    rows.iter()
+        .map(|row| row.chars().map(|c| c == '#').collect())
+        .collect()
+}
+
+fn draw(bitmap: &[Vec<bool>]) -> String {
+    bitmap
+        .iter()
+        .map(|row| {
+            let mut line = row
+                .iter()
+                .map(|&on| if on { '#' } else { '.' })
+                .collect::<String>();
+            line.push('\n');
+            line
+        })
+        .collect()
+}
+
+// The letters of the font for `height`, trimmed to their own width.
+fn font(height: usize) -> Vec<(char, Bitmap)> {
+    let trimmed = |(c, rows): (char, &[&str])| (c, trim_columns(&parse_glyph(rows)));
+
+    match height {
+        6 => SMALL_FONT
+            .iter()
+            .map(|(c, rows)| trimmed((*c, rows)))
+            .collect(),
+        10 => LARGE_FONT
+            .iter()
+            .map(|(c, rows)| trimmed((*c, rows)))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+// The widest letter drawn at column `x`, along with its width. Letters are usually
+// followed by a blank column, but wide ones such as `Y` can run straight into the next.
+fn lookup(font: &[(char, Bitmap)], bitmap: &[Vec<bool>], x: usize) -> Option<(char, usize)> {
+    let width = bitmap[0].len();
+
+    font.iter()
+        .filter(|(_, glyph)| {
+            let end = x + glyph[0].len();
+            end <= width && columns(bitmap, x, end) == *glyph
+        })
+        .map(|(c, glyph)| (*c, glyph[0].len()))
+        .max_by_key(|&(_, width)| width)
+}
+
+// Letters never contain a blank column, and each starts at the first lit column after
+// the previous one.
+fn recognize_bitmap(bitmap: &[Vec<bool>]) -> Result<String, OcrError> {
+    let is_blank_row = |row: &Vec<bool>| row.iter().all(|&on| !on);
+    let top = bitmap
+        .iter()
+        .position(|row| !is_blank_row(row))
+        .ok_or(OcrError::Empty)?;
+    let bottom = bitmap.iter().rposition(|row| !is_blank_row(row)).unwrap();
+    let bitmap = &bitmap[top..=bottom];
+
+    if bitmap.len() != 6 && bitmap.len() != 10 {
+        return Err(OcrError::UnsupportedHeight(bitmap.len()));
+    }
+
+    let font = font(bitmap.len());
+    let width = bitmap[0].len();
+    let mut letters = String::new();
+    let mut x = 0;
+
+    while x < width {
+        if column_is_blank(bitmap, x) {
+            x += 1;
+            continue;
+        }
+
+        let Some((letter, letter_width)) = lookup(&font, bitmap, x) else {
+            let end = (x..width)
+                .find(|&end| column_is_blank(bitmap, end))
+                .unwrap_or(width);
+
+            return Err(OcrError::UnknownGlyph {
+                index: letters.len(),
+                glyph: draw(&columns(bitmap, x, end)),
+            });
+        };
+
+        letters.push(letter);
+        x += letter_width;
+    }
+
+    Ok(letters)
+}
+
+/// Decodes the letters drawn by the `true` cells of `grid`.
+///
+/// # Errors
+///
+/// Returns an error if nothing is drawn, the letters aren't 6 or 10 cells tall, or a
+/// glyph doesn't match any known letter.
+pub fn recognize(grid: &Grid<bool>) -> Result<String, OcrError> {
+    recognize_bitmap(&grid.iter_rows().map(<[bool]>::to_vec).collect::<Vec<_>>())
+}
+
+/// Decodes the letters drawn by a set of lit points.
+///
+/// # Errors
+///
+/// See [`recognize`].
+pub fn recognize_points<'a>(
+    points: impl IntoIterator<Item = &'a GPoint>,
+) -> Result<String, OcrError> {
+    let points = points.into_iter().collect::<Vec<_>>();
+
+    if points.is_empty() {
+        return Err(OcrError::Empty);
+    }
+
+    let (min_x, max_x, min_y, max_y) = points.iter().fold(
+        (usize::MAX, 0, usize::MAX, 0),
+        |(min_x, max_x, min_y, max_y), p| {
+            (
+                min_x.min(p.x),
+                max_x.max(p.x),
+                min_y.min(p.y),
+                max_y.max(p.y),
+            )
+        },
+    );
+
+    let mut bitmap = vec![vec![false; max_x - min_x + 1]; max_y - min_y + 1];
+    for p in points {
+        bitmap[p.y - min_y][p.x - min_x] = true;
+    }
+
+    recognize_bitmap(&bitmap)
+}
+
+/// Decodes letters drawn with `#` or `█`; every other character is treated as blank.
+///
+/// # Errors
+///
+/// See [`recognize`].
+pub fn recognize_str(s: &str) -> Result<String, OcrError> {
+    let rows = s.lines().collect::<Vec<_>>();
+    let width = rows
+        .iter()
+        .map(|row| row.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let bitmap = rows
+        .iter()
+        .map(|row| {
+            let mut cells = row
+                .chars()
+                .map(|c| c == '#' || c == '█')
+                .collect::<Vec<_>>();
+            cells.resize(width, false);
+            cells
+        })
+        .collect::<Vec<_>>();
+
+    recognize_bitmap(&bitmap)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SMALL: &str = concat!(
+        "####...##.#..#.###..#..#.#....###..####.\n",
+        "#.......#.#..#.#..#.#..#.#....#..#....#.\n",
+        "###.....#.#..#.###..#..#.#....#..#...#..\n",
+        "#.......#.#..#.#..#.#..#.#....###...#...\n",
+        "#....#..#.#..#.#..#.#..#.#....#.#..#....\n",
+        "#.....##...##..###...##..####.#..#.####.\n",
+    );
+
+    #[test]
+    fn test_small_font() {
+        assert_eq!(recognize_str(SMALL), Ok("FJUBULRZ".to_string()));
+
+        let grid = Grid::new(
+            SMALL
+                .lines()
+                .map(|line| line.chars().map(|c| c == '#').collect())
+                .collect(),
+        );
+        assert_eq!(recognize(&grid), Ok("FJUBULRZ".to_string()));
+
+        let points = grid
+            .iter_points_values()
+            .filter(|&(_, &on)| on)
+            .map(|(p, _)| p)
+            .collect::<Vec<_>>();
+        assert_eq!(recognize_points(&points), Ok("FJUBULRZ".to_string()));
+
+        assert_eq!(
+            recognize_str(
+                ".###.#...#\n..#..#...#\n..#...#.#.\n..#....#..\n..#....#..\n.###...#..\n"
+            ),
+            Ok("IY".to_string())
+        );
+    }
+
+    #[test]
+    fn test_wide_letters() {
+        // Y fills all five columns of its cell, so nothing separates it from the next
+        // letter
+        let drawing = concat!(
+            "#...##..#.#...#.##..\n",
+            "#...##..#.#...##..#.\n",
+            ".#.#.####..#.#.#....\n",
+            "..#..#..#...#..#.##.\n",
+            "..#..#..#...#..#..#.\n",
+            "..#..#..#...#...###.\n",
+        );
+
+        assert_eq!(recognize_str(drawing), Ok("YHYG".to_string()));
+    }
+
+    #[test]
+    fn test_large_font() {
+        let drawing = LARGE_FONT
+            .iter()
+            .fold(vec![String::new(); 10], |mut rows, (_, glyph)| {
+                for (row, line) in rows.iter_mut().zip(glyph) {
+                    row.push_str(line);
+                    row.push_str("..");
+                }
+                rows
+            })
+            .join("\n");
+
+        assert_eq!(recognize_str(&drawing), Ok("ABCEFGHJKLNPRXZ".to_string()));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(recognize_str("....\n....\n"), Err(OcrError::Empty));
+        assert_eq!(recognize_points(&[]), Err(OcrError::Empty));
+        assert_eq!(
+            recognize_str("#\n#\n#\n"),
+            Err(OcrError::UnsupportedHeight(3))
+        );
+
+        let err =
+            recognize_str("#..#.##\n#..#.#.\n####.#.\n#..#.#.\n#..#.#.\n#..#.##\n").unwrap_err();
+        assert_eq!(
+            err,
+            OcrError::UnknownGlyph {
+                index: 1,
+                glyph: "##\n#.\n#.\n#.\n#.\n##\n".to_string()
+            }
+        );
+        assert!(err.to_string().starts_with("unknown glyph at position 1"));
+    }
+}
@@ -1,34 +1,41 @@
-use hashbrown::HashMap;
-use std::cmp::Reverse;
 use std::collections::VecDeque;
 use std::hash::Hash;
 
-use priority_queue::PriorityQueue;
+use hashbrown::{HashMap, HashSet};
+use num_traits::Zero;
 
-fn reconstruct_path<State, GetCost>(
-    get_cost: GetCost,
-    mut came_from: HashMap<State, State>,
-    end: State,
-) -> (Vec<State>, usize)
+use crate::search::Tracer;
+
+use arena::Arena;
+use queue::{BucketQueue, Frontier, HeapQueue};
+
+mod arena;
+mod optimal;
+mod queue;
+
+pub use optimal::{a_star_all, a_star_all_traced, dijkstra_all, OptimalPaths};
+
+fn reconstruct_path<State>(mut came_from: HashMap<State, State>, end: State) -> Vec<State>
 where
-    State: Hash + Eq + Clone,
-    GetCost: Fn(&State, &State) -> usize,
+    State: Hash + Eq,
 {
     let mut result = VecDeque::new();
-    let mut cost = 0;
-
     let mut current = end;
 
     while let Some(next) = came_from.remove(&current) {
-        cost += get_cost(&next, &current);
         result.push_front(current);
         current = next;
     }
     result.push_front(current);
 
-    (result.into(), cost)
+    result.into()
 }
 
+// Anything that can be summed along a path and compared, e.g. `usize`, `u32` or `i64`.
+pub trait Cost: Copy + Ord + Zero {}
+
+impl<T> Cost for T where T: Copy + Ord + Zero {}
+
 // https://en.wikipedia.org/wiki/A*_search_algorithm
 pub fn a_star<State, NextStateFn, HeuristicFn, CostFn, GoalFn>(
     starts: &[State],
@@ -44,43 +51,425 @@ where
     HeuristicFn: Fn(&State) -> usize,
     CostFn: Fn(&State, &State) -> usize,
 {
-    let mut open_set = PriorityQueue::new();
-    let mut came_from = HashMap::new();
-    let mut g_score = HashMap::new();
+    a_star_edges(
+        starts,
+        is_goal,
+        |state| {
+            get_next_states(state)
+                .into_iter()
+                .map(|next| {
+                    let cost = get_cost(state, &next);
+                    (next, cost)
+                })
+                .collect::<Vec<_>>()
+        },
+        heuristic_fn,
+    )
+}
+
+// Like `a_star`, but `get_edges` yields each next state together with the cost of
+// moving to it.
+pub fn a_star_edges<State, C, EdgesFn, Edges, HeuristicFn, GoalFn>(
+    starts: &[State],
+    is_goal: GoalFn,
+    get_edges: EdgesFn,
+    heuristic_fn: HeuristicFn,
+) -> Option<(Vec<State>, C)>
+where
+    State: Hash + Eq + Clone,
+    C: Cost,
+    GoalFn: Fn(&State) -> bool,
+    EdgesFn: Fn(&State) -> Edges,
+    Edges: IntoIterator<Item = (State, C)>,
+    HeuristicFn: Fn(&State) -> C,
+{
+    a_star_traced(starts, is_goal, get_edges, heuristic_fn, &mut Tracer::new())
+}
+
+// Like `a_star_edges`, but reports what the search did to `tracer`.
+pub fn a_star_traced<State, C, EdgesFn, Edges, HeuristicFn, GoalFn>(
+    starts: &[State],
+    is_goal: GoalFn,
+    get_edges: EdgesFn,
+    heuristic_fn: HeuristicFn,
+    tracer: &mut Tracer<State>,
+) -> Option<(Vec<State>, C)>
+where
+    State: Hash + Eq + Clone,
+    C: Cost,
+    GoalFn: Fn(&State) -> bool,
+    EdgesFn: Fn(&State) -> Edges,
+    Edges: IntoIterator<Item = (State, C)>,
+    HeuristicFn: Fn(&State) -> C,
+{
+    tracer.time(|tracer| {
+        search(
+            starts,
+            is_goal,
+            get_edges,
+            heuristic_fn,
+            HeapQueue::new(),
+            tracer,
+        )
+    })
+}
+
+// Like `a_star_edges`, but keeps the open set in one bucket per priority. This beats a
+// heap when costs are small integers and `heuristic_fn` is consistent, as when moving
+// across a grid of digits.
+pub fn a_star_buckets<State, EdgesFn, Edges, HeuristicFn, GoalFn>(
+    starts: &[State],
+    is_goal: GoalFn,
+    get_edges: EdgesFn,
+    heuristic_fn: HeuristicFn,
+) -> Option<(Vec<State>, usize)>
+where
+    State: Hash + Eq + Clone,
+    GoalFn: Fn(&State) -> bool,
+    EdgesFn: Fn(&State) -> Edges,
+    Edges: IntoIterator<Item = (State, usize)>,
+    HeuristicFn: Fn(&State) -> usize,
+{
+    a_star_buckets_traced(starts, is_goal, get_edges, heuristic_fn, &mut Tracer::new())
+}
+
+pub fn a_star_buckets_traced<State, EdgesFn, Edges, HeuristicFn, GoalFn>(
+    starts: &[State],
+    is_goal: GoalFn,
+    get_edges: EdgesFn,
+    heuristic_fn: HeuristicFn,
+    tracer: &mut Tracer<State>,
+) -> Option<(Vec<State>, usize)>
+where
+    State: Hash + Eq + Clone,
+    GoalFn: Fn(&State) -> bool,
+    EdgesFn: Fn(&State) -> Edges,
+    Edges: IntoIterator<Item = (State, usize)>,
+    HeuristicFn: Fn(&State) -> usize,
+{
+    tracer.time(|tracer| {
+        search(
+            starts,
+            is_goal,
+            get_edges,
+            heuristic_fn,
+            BucketQueue::new(),
+            tracer,
+        )
+    })
+}
+
+fn search<State, C, EdgesFn, Edges, HeuristicFn, GoalFn, Queue>(
+    starts: &[State],
+    is_goal: GoalFn,
+    get_edges: EdgesFn,
+    heuristic_fn: HeuristicFn,
+    mut open_set: Queue,
+    tracer: &mut Tracer<State>,
+) -> Option<(Vec<State>, C)>
+where
+    State: Hash + Eq + Clone,
+    C: Cost,
+    GoalFn: Fn(&State) -> bool,
+    EdgesFn: Fn(&State) -> Edges,
+    Edges: IntoIterator<Item = (State, C)>,
+    HeuristicFn: Fn(&State) -> C,
+    Queue: Frontier<C>,
+{
+    let mut arena = Arena::new();
 
     for start in starts {
-        open_set.push(start.clone(), Reverse(0));
-        g_score.insert(start.clone(), 0_usize);
+        let (id, _) = arena.intern(start.clone(), C::zero());
+        open_set.push(heuristic_fn(start), id, C::zero());
     }
+    tracer.frontier(open_set.len());
 
-    while let Some((current, _)) = open_set.pop() {
-        if is_goal(&current) {
-            return Some(reconstruct_path(get_cost, came_from, current));
+    while let Some((current, g_score)) = open_set.pop() {
+        if g_score > arena.g_score(current) {
+            continue;
         }
 
-        for neigh in get_next_states(&current) {
-            let tentative_g_score = g_score[&current] + get_cost(&current, &neigh);
+        tracer.expand(arena.state(current));
 
-            if tentative_g_score < *g_score.get(&neigh).unwrap_or(&usize::MAX) {
-                came_from.insert(neigh.clone(), current.clone());
-                g_score.insert(neigh.clone(), tentative_g_score);
+        if is_goal(arena.state(current)) {
+            return Some((arena.path(current), g_score));
+        }
+
+        for (neigh, edge_cost) in get_edges(arena.state(current)) {
+            let tentative_g_score = g_score + edge_cost;
+            let f_score = tentative_g_score + heuristic_fn(&neigh);
+            let (id, is_new) = arena.intern(neigh, tentative_g_score);
 
-                let f_score = tentative_g_score + heuristic_fn(&neigh);
-                if open_set.change_priority(&neigh, Reverse(f_score)).is_none() {
-                    open_set.push(neigh, Reverse(f_score));
+            if is_new || tentative_g_score < arena.g_score(id) {
+                if !is_new {
+                    tracer.duplicate();
                 }
+
+                arena.update(id, current, tentative_g_score);
+                open_set.push(f_score, id, tentative_g_score);
             }
         }
+
+        tracer.frontier(open_set.len());
     }
 
     None
 }
 
+// Dijkstra's algorithm is A* without a heuristic.
+pub fn dijkstra<State, NextStateFn, CostFn, GoalFn>(
+    starts: &[State],
+    is_goal: GoalFn,
+    get_cost: CostFn,
+    get_next_states: NextStateFn,
+) -> Option<(Vec<State>, usize)>
+where
+    State: Hash + Eq + Clone,
+    GoalFn: Fn(&State) -> bool,
+    NextStateFn: Fn(&State) -> Vec<State>,
+    CostFn: Fn(&State, &State) -> usize,
+{
+    a_star(starts, is_goal, get_cost, get_next_states, |_| 0)
+}
+
+pub fn dijkstra_edges<State, C, EdgesFn, Edges, GoalFn>(
+    starts: &[State],
+    is_goal: GoalFn,
+    get_edges: EdgesFn,
+) -> Option<(Vec<State>, C)>
+where
+    State: Hash + Eq + Clone,
+    C: Cost,
+    GoalFn: Fn(&State) -> bool,
+    EdgesFn: Fn(&State) -> Edges,
+    Edges: IntoIterator<Item = (State, C)>,
+{
+    a_star_edges(starts, is_goal, get_edges, |_| C::zero())
+}
+
+pub fn dijkstra_traced<State, C, EdgesFn, Edges, GoalFn>(
+    starts: &[State],
+    is_goal: GoalFn,
+    get_edges: EdgesFn,
+    tracer: &mut Tracer<State>,
+) -> Option<(Vec<State>, C)>
+where
+    State: Hash + Eq + Clone,
+    C: Cost,
+    GoalFn: Fn(&State) -> bool,
+    EdgesFn: Fn(&State) -> Edges,
+    Edges: IntoIterator<Item = (State, C)>,
+{
+    a_star_traced(starts, is_goal, get_edges, |_| C::zero(), tracer)
+}
+
+// The cost of the cheapest path from any of `starts` to every reachable state.
+pub fn dijkstra_distances<State, C, EdgesFn, Edges>(
+    starts: &[State],
+    get_edges: EdgesFn,
+) -> HashMap<State, C>
+where
+    State: Hash + Eq + Clone,
+    C: Cost,
+    EdgesFn: Fn(&State) -> Edges,
+    Edges: IntoIterator<Item = (State, C)>,
+{
+    let mut arena = Arena::new();
+    let mut open_set = HeapQueue::new();
+
+    for start in starts {
+        let (id, _) = arena.intern(start.clone(), C::zero());
+        open_set.push(C::zero(), id, C::zero());
+    }
+
+    while let Some((current, g_score)) = open_set.pop() {
+        if g_score > arena.g_score(current) {
+            continue;
+        }
+
+        for (neigh, edge_cost) in get_edges(arena.state(current)) {
+            let tentative_g_score = g_score + edge_cost;
+            let (id, is_new) = arena.intern(neigh, tentative_g_score);
+
+            if is_new || tentative_g_score < arena.g_score(id) {
+                arena.update(id, current, tentative_g_score);
+                open_set.push(tentative_g_score, id, tentative_g_score);
+            }
+        }
+    }
+
+    arena.into_scores().collect()
+}
+
+// The number of steps from the nearest of `starts` to every reachable state.
+pub fn bfs_distances<State, NextStateFn>(
+    starts: &[State],
+    get_next_states: NextStateFn,
+) -> HashMap<State, usize>
+where
+    State: Hash + Eq + Clone,
+    NextStateFn: Fn(&State) -> Vec<State>,
+{
+    let mut queue = VecDeque::new();
+    let mut distances = HashMap::new();
+
+    for start in starts {
+        if !distances.contains_key(start) {
+            distances.insert(start.clone(), 0);
+            queue.push_back(start.clone());
+        }
+    }
+
+    while let Some(current) = queue.pop_front() {
+        let distance = distances[&current] + 1;
+
+        for neigh in get_next_states(&current) {
+            if !distances.contains_key(&neigh) {
+                distances.insert(neigh.clone(), distance);
+                queue.push_back(neigh);
+            }
+        }
+    }
+
+    distances
+}
+
+// Finds a path with the fewest steps; its cost is the number of steps.
+pub fn bfs<State, NextStateFn, GoalFn>(
+    starts: &[State],
+    is_goal: GoalFn,
+    get_next_states: NextStateFn,
+) -> Option<(Vec<State>, usize)>
+where
+    State: Hash + Eq + Clone,
+    GoalFn: Fn(&State) -> bool,
+    NextStateFn: Fn(&State) -> Vec<State>,
+{
+    bfs_traced(starts, is_goal, get_next_states, &mut Tracer::new())
+}
+
+pub fn bfs_traced<State, NextStateFn, GoalFn>(
+    starts: &[State],
+    is_goal: GoalFn,
+    get_next_states: NextStateFn,
+    tracer: &mut Tracer<State>,
+) -> Option<(Vec<State>, usize)>
+where
+    State: Hash + Eq + Clone,
+    GoalFn: Fn(&State) -> bool,
+    NextStateFn: Fn(&State) -> Vec<State>,
+{
+    tracer.time(|tracer| {
+        let mut queue = VecDeque::new();
+        let mut came_from = HashMap::new();
+        let mut seen = HashSet::new();
+
+        for start in starts {
+            if seen.insert(start.clone()) {
+                queue.push_back(start.clone());
+            }
+        }
+        tracer.frontier(queue.len());
+
+        while let Some(current) = queue.pop_front() {
+            tracer.expand(&current);
+
+            if is_goal(&current) {
+                let path = reconstruct_path(came_from, current);
+                let steps = path.len() - 1;
+                return Some((path, steps));
+            }
+
+            for neigh in get_next_states(&current) {
+                if seen.insert(neigh.clone()) {
+                    came_from.insert(neigh.clone(), current.clone());
+                    queue.push_back(neigh);
+                } else {
+                    tracer.duplicate();
+                }
+            }
+
+            tracer.frontier(queue.len());
+        }
+
+        None
+    })
+}
+
+// Finds any path, exploring depth-first; its cost is the number of steps.
+pub fn dfs<State, NextStateFn, GoalFn>(
+    starts: &[State],
+    is_goal: GoalFn,
+    get_next_states: NextStateFn,
+) -> Option<(Vec<State>, usize)>
+where
+    State: Hash + Eq + Clone,
+    GoalFn: Fn(&State) -> bool,
+    NextStateFn: Fn(&State) -> Vec<State>,
+{
+    dfs_traced(starts, is_goal, get_next_states, &mut Tracer::new())
+}
+
+pub fn dfs_traced<State, NextStateFn, GoalFn>(
+    starts: &[State],
+    is_goal: GoalFn,
+    get_next_states: NextStateFn,
+    tracer: &mut Tracer<State>,
+) -> Option<(Vec<State>, usize)>
+where
+    State: Hash + Eq + Clone,
+    GoalFn: Fn(&State) -> bool,
+    NextStateFn: Fn(&State) -> Vec<State>,
+{
+    tracer.time(|tracer| {
+        let mut stack = vec![];
+        let mut came_from = HashMap::new();
+        let mut seen = HashSet::new();
+
+        for start in starts.iter().rev() {
+            stack.push(start.clone());
+        }
+        tracer.frontier(stack.len());
+
+        while let Some(current) = stack.pop() {
+            // reached again through another state before this entry came up
+            if !seen.insert(current.clone()) {
+                tracer.duplicate();
+                continue;
+            }
+
+            tracer.expand(&current);
+
+            if is_goal(&current) {
+                let path = reconstruct_path(came_from, current);
+                let steps = path.len() - 1;
+                return Some((path, steps));
+            }
+
+            // pushed in reverse so the first successor is explored first
+            for neigh in get_next_states(&current).into_iter().rev() {
+                if !seen.contains(&neigh) {
+                    came_from.insert(neigh.clone(), current.clone());
+                    stack.push(neigh);
+                }
+            }
+
+            tracer.frontier(stack.len());
+        }
+
+        None
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use crate::grid::{GPoint, Grid};
     use crate::p2;
-    use crate::pathfinding::a_star;
+    use crate::pathfinding::{
+        a_star, a_star_buckets, a_star_edges, a_star_traced, bfs, bfs_distances, bfs_traced, dfs,
+        dfs_traced, dijkstra, dijkstra_distances, dijkstra_edges, dijkstra_traced,
+    };
+    use crate::search::Tracer;
     use crate::vector2::Point2;
 
     #[test]
@@ -108,4 +497,237 @@ mod tests {
 
         assert!(valid_paths.contains(&path));
     }
+
+    #[test]
+    fn test_dijkstra() {
+        let grid: Grid<u8> = Grid::new(vec![vec![10, 1, 1], vec![9, 50, 9], vec![1, 1, 3]]);
+        let goal = p2!(2, 2);
+
+        let (path, cost) = dijkstra(
+            &[GPoint::default()],
+            |p| *p == goal,
+            |_, dst| usize::from(grid[dst]),
+            |state| grid.all_neighbors(state, false),
+        )
+        .unwrap();
+
+        assert_eq!(cost, 14);
+        assert_eq!(path.first(), Some(&p2!(0, 0)));
+        assert_eq!(path.last(), Some(&goal));
+    }
+
+    #[test]
+    fn test_bfs() {
+        let grid: Grid<char> = "..#.\n..#.\n....\n".parse().unwrap();
+        let next = |p: &GPoint| {
+            grid.all_neighbors(p, false)
+                .into_iter()
+                .filter(|&n| grid[n] != '#')
+                .collect()
+        };
+
+        let (path, cost) = bfs(&[p2!(0, 0)], |p| *p == p2!(3, 0), next).unwrap();
+        assert_eq!(cost, 7);
+        assert_eq!(path.len(), 8);
+        assert!(path.windows(2).all(|w| w[0].manhattan_dist(&w[1]) == 1));
+
+        // the closest of several starts wins
+        let (path, cost) = bfs(&[p2!(0, 0), p2!(3, 2)], |p| p.y == 0 && p.x == 3, next).unwrap();
+        assert_eq!(cost, 2);
+        assert_eq!(path, vec![p2!(3, 2), p2!(3, 1), p2!(3, 0)]);
+
+        assert_eq!(bfs(&[p2!(0, 0)], |&p| grid[p] == '#', next), None);
+        assert_eq!(
+            bfs(&[p2!(1, 1)], |_| true, next),
+            Some((vec![p2!(1, 1)], 0))
+        );
+    }
+
+    #[test]
+    fn test_dfs() {
+        let (path, cost) = dfs(
+            &[0_u32],
+            |&n| n == 5,
+            |&n| if n < 10 { vec![n + 1, n + 2] } else { vec![] },
+        )
+        .unwrap();
+
+        assert_eq!(path, vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(cost, 5);
+
+        assert_eq!(dfs(&[1_u32], |&n| n == 0, |&n| vec![n * 2 % 7]), None);
+    }
+
+    #[test]
+    fn test_edges() {
+        // a step of 1 costs 3 and a jump of 3 costs 4
+        let (path, cost) =
+            dijkstra_edges(&[0_i32], |&n| n == 7, |&n| [(n + 1, 3_u64), (n + 3, 4)]).unwrap();
+        assert_eq!(cost, 11);
+        assert_eq!(path.len(), 4);
+        assert_eq!(path.windows(2).filter(|w| w[1] - w[0] == 3).count(), 2);
+
+        // moving up to three cells in a line costs the sum of the cells entered
+        let grid: Grid<u8> = Grid::new(vec![vec![1, 9, 9, 9], vec![1, 1, 1, 1], vec![9, 9, 9, 1]]);
+        let goal = p2!(3, 2);
+
+        let (path, cost) = a_star_edges(
+            &[GPoint::default()],
+            |p| *p == goal,
+            |&p| {
+                let mut edges = vec![];
+
+                for direction in crate::vector2::FOUR_NEIGHBORS {
+                    let mut cost = 0_u32;
+                    let mut current = p;
+
+                    for _ in 0..3 {
+                        let Some(next) = grid.get_neighbor(&current, direction) else {
+                            break;
+                        };
+                        cost += u32::from(grid[next]);
+                        current = next;
+                        edges.push((current, cost));
+                    }
+                }
+
+                edges
+            },
+            |p| u32::try_from(p.manhattan_dist(&goal)).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(cost, 5);
+        assert_eq!(path, vec![p2!(0, 0), p2!(0, 1), p2!(3, 1), p2!(3, 2)]);
+    }
+
+    #[test]
+    fn test_buckets() {
+        let grid: Grid<u8> = Grid::new(vec![vec![10, 1, 1], vec![9, 50, 9], vec![1, 1, 3]]);
+        let goal = p2!(2, 2);
+
+        let (path, cost) = a_star_buckets(
+            &[GPoint::default()],
+            |p| *p == goal,
+            |p| {
+                grid.all_neighbors(p, false)
+                    .into_iter()
+                    .map(|n| (n, usize::from(grid[n])))
+                    .collect::<Vec<_>>()
+            },
+            |p| p.manhattan_dist(&goal),
+        )
+        .unwrap();
+
+        assert_eq!(cost, 14);
+        assert_eq!(path.len(), 5);
+
+        assert_eq!(
+            a_star_buckets(
+                &[0_usize],
+                |&n| n == 3,
+                |&n| (n < 10).then_some((n + 2, 1)),
+                |_| 0
+            ),
+            None::<(Vec<usize>, usize)>
+        );
+    }
+
+    #[test]
+    fn test_distances() {
+        let distances = dijkstra_distances(&[0_u32], |&n| {
+            [(n + 1, 5_u32), (n + 2, 3)]
+                .into_iter()
+                .filter(|&(n, _)| n <= 4)
+        });
+
+        assert_eq!(distances.len(), 5);
+        assert_eq!(distances[&0], 0);
+        assert_eq!(distances[&1], 5);
+        assert_eq!(distances[&3], 8);
+        assert_eq!(distances[&4], 6);
+
+        let grid: Grid<char> = "..#\n..#\n#..\n".parse().unwrap();
+        let distances = bfs_distances(&[p2!(0, 0)], |p| {
+            grid.all_neighbors(p, false)
+                .into_iter()
+                .filter(|&n| grid[n] != '#')
+                .collect()
+        });
+
+        assert_eq!(distances.len(), 6);
+        assert_eq!(distances[&p2!(2, 2)], 4);
+        assert!(!distances.contains_key(&p2!(2, 0)));
+    }
+
+    #[test]
+    fn test_traced() {
+        let grid: Grid<char> = "...\n.#.\n...\n".parse().unwrap();
+        let edges = |p: &GPoint| {
+            grid.all_neighbors(p, false)
+                .into_iter()
+                .filter(|&n| grid[n] != '#')
+                .map(|n| (n, 1_usize))
+                .collect::<Vec<_>>()
+        };
+
+        let mut order = vec![];
+        let mut tracer = Tracer::with_observer(|p: &GPoint| order.push(*p));
+
+        let (_, cost) = a_star_traced(
+            &[p2!(0, 0)],
+            |p| *p == p2!(2, 2),
+            edges,
+            |p| p.manhattan_dist(&p2!(2, 2)),
+            &mut tracer,
+        )
+        .unwrap();
+        let stats = tracer.stats();
+        drop(tracer);
+
+        assert_eq!(cost, 4);
+        assert_eq!(stats.expanded, order.len());
+        assert_eq!(order.first(), Some(&p2!(0, 0)));
+        assert_eq!(order.last(), Some(&p2!(2, 2)));
+        assert!(stats.max_frontier >= 2);
+
+        // an unreachable goal still shows how much got explored
+        let mut tracer = Tracer::new();
+        assert_eq!(
+            a_star_traced(&[p2!(0, 0)], |p| *p == p2!(1, 1), edges, |_| 0, &mut tracer),
+            None
+        );
+        assert_eq!(tracer.stats().expanded, 8);
+        assert_eq!(tracer.stats().duplicates, 0);
+    }
+
+    #[test]
+    fn test_traced_duplicates() {
+        // 1 is first pushed at cost 10, then again at cost 2 through 2
+        let edges = |&n: &u32| match n {
+            0 => vec![(1, 10), (2, 1)],
+            2 => vec![(1, 1)],
+            _ => vec![],
+        };
+
+        let mut tracer = Tracer::new();
+        let (path, cost) = dijkstra_traced(&[0], |&n| n == 1, edges, &mut tracer).unwrap();
+
+        assert_eq!((path, cost), (vec![0, 2, 1], 2));
+        assert_eq!(tracer.stats().duplicates, 1);
+        assert_eq!(tracer.stats().expanded, 3);
+
+        let next = |&n: &u32| if n < 10 { vec![n + 1, n + 2] } else { vec![] };
+
+        // 2, 3 and 4 are each reached a second time from the state before them
+        let mut tracer = Tracer::new();
+        bfs_traced(&[0], |&n| n == 4, next, &mut tracer).unwrap();
+        assert_eq!(tracer.stats().duplicates, 3);
+        assert_eq!(tracer.stats().expanded, 5);
+
+        let mut tracer = Tracer::new();
+        dfs_traced(&[0], |&n| n == 20, next, &mut tracer);
+        assert_eq!(tracer.stats().expanded, 12);
+        assert!(tracer.stats().duplicates > 0);
+    }
 }
@@ -0,0 +1,102 @@
+use std::hash::Hash;
+
+use hashbrown::HashMap;
+
+const NO_PARENT: usize = usize::MAX;
+
+// Interns each state once and keys everything else by its index, so the search only
+// clones a state the first time it's seen.
+pub(crate) struct Arena<State, C> {
+    ids: HashMap<State, usize>,
+    states: Vec<State>,
+    parents: Vec<usize>,
+    g_scores: Vec<C>,
+}
+
+impl<State, C> Arena<State, C>
+where
+    State: Hash + Eq + Clone,
+    C: Copy,
+{
+    pub(crate) fn new() -> Self {
+        Self {
+            ids: HashMap::new(),
+            states: vec![],
+            parents: vec![],
+            g_scores: vec![],
+        }
+    }
+
+    pub(crate) fn state(&self, id: usize) -> &State {
+        &self.states[id]
+    }
+
+    pub(crate) fn g_score(&self, id: usize) -> C {
+        self.g_scores[id]
+    }
+
+    pub(crate) fn parent(&self, id: usize) -> Option<usize> {
+        Some(self.parents[id]).filter(|&parent| parent != NO_PARENT)
+    }
+
+    // Returns the id of `state` and whether it was newly added, in which case its score
+    // is `g_score` and it has no parent.
+    pub(crate) fn intern(&mut self, state: State, g_score: C) -> (usize, bool) {
+        if let Some(&id) = self.ids.get(&state) {
+            return (id, false);
+        }
+
+        let id = self.states.len();
+        self.ids.insert(state.clone(), id);
+        self.states.push(state);
+        self.parents.push(NO_PARENT);
+        self.g_scores.push(g_score);
+
+        (id, true)
+    }
+
+    pub(crate) fn update(&mut self, id: usize, parent: usize, g_score: C) {
+        self.parents[id] = parent;
+        self.g_scores[id] = g_score;
+    }
+
+    pub(crate) fn into_scores(self) -> impl Iterator<Item = (State, C)> {
+        self.states.into_iter().zip(self.g_scores)
+    }
+
+    pub(crate) fn path(&self, end: usize) -> Vec<State> {
+        let mut ids = vec![end];
+        while let Some(parent) = self.parent(*ids.last().unwrap()) {
+            ids.push(parent);
+        }
+
+        ids.into_iter()
+            .rev()
+            .map(|id| self.states[id].clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arena() {
+        let mut arena = Arena::new();
+
+        assert_eq!(arena.intern("a", 0), (0, true));
+        assert_eq!(arena.intern("b", 5), (1, true));
+        assert_eq!(arena.intern("a", 9), (0, false));
+        assert_eq!(arena.g_score(0), 0);
+
+        let (c, _) = arena.intern("c", 7);
+        arena.update(1, 0, 4);
+        arena.update(c, 1, 6);
+
+        assert_eq!(arena.g_score(1), 4);
+        assert_eq!(arena.parent(0), None);
+        assert_eq!(arena.state(c), &"c");
+        assert_eq!(arena.path(c), vec!["a", "b", "c"]);
+    }
+}
@@ -0,0 +1,312 @@
+use std::hash::Hash;
+
+use super::arena::Arena;
+use super::queue::{Frontier, HeapQueue};
+use super::Cost;
+use crate::search::Tracer;
+
+// Every optimal path found by `a_star_all`, stored as a graph of equal-cost
+// predecessors so paths can be counted without enumerating them.
+pub struct OptimalPaths<State, C> {
+    arena: Arena<State, C>,
+    predecessors: Vec<Vec<usize>>,
+    goals: Vec<usize>,
+    cost: C,
+}
+
+impl<State, C> OptimalPaths<State, C>
+where
+    State: Hash + Eq + Clone,
+    C: Cost,
+{
+    #[must_use]
+    pub fn cost(&self) -> C {
+        self.cost
+    }
+
+    // Goal states reached at the optimal cost.
+    pub fn goals(&self) -> impl Iterator<Item = &State> + '_ {
+        self.goals.iter().map(|&id| self.arena.state(id))
+    }
+
+    // Each state on at least one optimal path, once.
+    #[must_use]
+    pub fn states(&self) -> Vec<&State> {
+        self.ids_on_paths()
+            .into_iter()
+            .map(|id| self.arena.state(id))
+            .collect()
+    }
+
+    #[must_use]
+    pub fn count(&self) -> usize {
+        let mut ids = self.ids_on_paths();
+        ids.sort_unstable_by_key(|&id| self.arena.g_score(id));
+
+        let mut counts = vec![0; self.predecessors.len()];
+
+        for id in ids {
+            counts[id] = if self.predecessors[id].is_empty() {
+                1
+            } else {
+                self.predecessors[id].iter().map(|&p| counts[p]).sum()
+            };
+        }
+
+        self.goals.iter().map(|&id| counts[id]).sum()
+    }
+
+    // Lazily walks every optimal path, from a start to a goal.
+    pub fn paths(&self) -> impl Iterator<Item = Vec<State>> + '_ {
+        let mut stack = self.goals.iter().map(|&id| vec![id]).collect::<Vec<_>>();
+
+        std::iter::from_fn(move || {
+            while let Some(reversed) = stack.pop() {
+                let last = *reversed.last().unwrap();
+
+                if self.predecessors[last].is_empty() {
+                    return Some(
+                        reversed
+                            .into_iter()
+                            .rev()
+                            .map(|id| self.arena.state(id).clone())
+                            .collect(),
+                    );
+                }
+
+                for &p in &self.predecessors[last] {
+                    let mut extended = reversed.clone();
+                    extended.push(p);
+                    stack.push(extended);
+                }
+            }
+
+            None
+        })
+    }
+
+    fn ids_on_paths(&self) -> Vec<usize> {
+        let mut seen = vec![false; self.predecessors.len()];
+        let mut stack = self.goals.clone();
+        let mut ids = vec![];
+
+        while let Some(id) = stack.pop() {
+            if !seen[id] {
+                seen[id] = true;
+                ids.push(id);
+                stack.extend(&self.predecessors[id]);
+            }
+        }
+
+        ids
+    }
+}
+
+// Like `a_star_edges`, but records every predecessor that reaches a state at its best
+// cost and keeps going until every goal at the optimal cost has been found. Edge costs
+// must be positive and `heuristic_fn` consistent.
+pub fn a_star_all<State, C, EdgesFn, Edges, HeuristicFn, GoalFn>(
+    starts: &[State],
+    is_goal: GoalFn,
+    get_edges: EdgesFn,
+    heuristic_fn: HeuristicFn,
+) -> Option<OptimalPaths<State, C>>
+where
+    State: Hash + Eq + Clone,
+    C: Cost,
+    GoalFn: Fn(&State) -> bool,
+    EdgesFn: Fn(&State) -> Edges,
+    Edges: IntoIterator<Item = (State, C)>,
+    HeuristicFn: Fn(&State) -> C,
+{
+    a_star_all_traced(starts, is_goal, get_edges, heuristic_fn, &mut Tracer::new())
+}
+
+pub fn a_star_all_traced<State, C, EdgesFn, Edges, HeuristicFn, GoalFn>(
+    starts: &[State],
+    is_goal: GoalFn,
+    get_edges: EdgesFn,
+    heuristic_fn: HeuristicFn,
+    tracer: &mut Tracer<State>,
+) -> Option<OptimalPaths<State, C>>
+where
+    State: Hash + Eq + Clone,
+    C: Cost,
+    GoalFn: Fn(&State) -> bool,
+    EdgesFn: Fn(&State) -> Edges,
+    Edges: IntoIterator<Item = (State, C)>,
+    HeuristicFn: Fn(&State) -> C,
+{
+    tracer.time(|tracer| search_all(starts, is_goal, get_edges, heuristic_fn, tracer))
+}
+
+fn search_all<State, C, EdgesFn, Edges, HeuristicFn, GoalFn>(
+    starts: &[State],
+    is_goal: GoalFn,
+    get_edges: EdgesFn,
+    heuristic_fn: HeuristicFn,
+    tracer: &mut Tracer<State>,
+) -> Option<OptimalPaths<State, C>>
+where
+    State: Hash + Eq + Clone,
+    C: Cost,
+    GoalFn: Fn(&State) -> bool,
+    EdgesFn: Fn(&State) -> Edges,
+    Edges: IntoIterator<Item = (State, C)>,
+    HeuristicFn: Fn(&State) -> C,
+{
+    let mut arena = Arena::new();
+    let mut predecessors: Vec<Vec<usize>> = vec![];
+    let mut open_set = HeapQueue::new();
+    let mut goals = vec![];
+    let mut best = None;
+
+    for start in starts {
+        let (id, is_new) = arena.intern(start.clone(), C::zero());
+        if is_new {
+            predecessors.push(vec![]);
+            open_set.push(heuristic_fn(start), id, C::zero());
+        }
+    }
+    tracer.frontier(open_set.len());
+
+    while let Some((current, g_score)) = open_set.pop() {
+        if g_score > arena.g_score(current) {
+            continue;
+        }
+
+        let state = arena.state(current);
+
+        if best.is_some_and(|best| g_score + heuristic_fn(state) > best) {
+            break;
+        }
+
+        tracer.expand(state);
+
+        if is_goal(state) {
+            best = Some(g_score);
+            goals.push(current);
+            continue;
+        }
+
+        for (neigh, edge_cost) in get_edges(state) {
+            let tentative_g_score = g_score + edge_cost;
+            let f_score = tentative_g_score + heuristic_fn(&neigh);
+            let (id, is_new) = arena.intern(neigh, tentative_g_score);
+
+            if is_new {
+                predecessors.push(vec![current]);
+            } else if tentative_g_score < arena.g_score(id) {
+                predecessors[id] = vec![current];
+                tracer.duplicate();
+            } else {
+                if tentative_g_score == arena.g_score(id) {
+                    predecessors[id].push(current);
+                }
+                continue;
+            }
+
+            arena.update(id, current, tentative_g_score);
+            open_set.push(f_score, id, tentative_g_score);
+        }
+
+        tracer.frontier(open_set.len());
+    }
+
+    best.map(|cost| OptimalPaths {
+        arena,
+        predecessors,
+        goals,
+        cost,
+    })
+}
+
+pub fn dijkstra_all<State, C, EdgesFn, Edges, GoalFn>(
+    starts: &[State],
+    is_goal: GoalFn,
+    get_edges: EdgesFn,
+) -> Option<OptimalPaths<State, C>>
+where
+    State: Hash + Eq + Clone,
+    C: Cost,
+    GoalFn: Fn(&State) -> bool,
+    EdgesFn: Fn(&State) -> Edges,
+    Edges: IntoIterator<Item = (State, C)>,
+{
+    a_star_all(starts, is_goal, get_edges, |_| C::zero())
+}
+
+#[cfg(test)]
+mod tests {
+    use hashbrown::HashSet;
+
+    use super::*;
+    use crate::grid::{GPoint, Grid};
+    use crate::p2;
+    use crate::vector2::Point2;
+
+    fn open_neighbors(grid: &Grid<char>, p: GPoint) -> Vec<(GPoint, usize)> {
+        grid.all_neighbors(&p, false)
+            .into_iter()
+            .filter(|&n| grid[n] != '#')
+            .map(|n| (n, 1))
+            .collect()
+    }
+
+    #[test]
+    fn test_open_room() {
+        let grid: Grid<char> = "...\n...\n...\n".parse().unwrap();
+        let goal = p2!(2, 2);
+
+        let paths = a_star_all(
+            &[p2!(0, 0)],
+            |p| *p == goal,
+            |&p| open_neighbors(&grid, p),
+            |p| p.manhattan_dist(&goal),
+        )
+        .unwrap();
+
+        assert_eq!(paths.cost(), 4);
+        assert_eq!(paths.count(), 6);
+        assert_eq!(paths.states().len(), 9);
+
+        let all = paths.paths().collect::<HashSet<_>>();
+        assert_eq!(all.len(), 6);
+        assert!(all.iter().all(|path| path.len() == 5 && path[4] == goal));
+    }
+
+    #[test]
+    fn test_multiple_goals_and_starts() {
+        let grid: Grid<char> = "....\n.##.\n....\n".parse().unwrap();
+
+        // around either side of the wall to the corner on that side
+        let paths = dijkstra_all(
+            &[p2!(0, 1)],
+            |p: &GPoint| p.x == 3 && p.y != 1,
+            |&p| open_neighbors(&grid, p),
+        )
+        .unwrap();
+
+        assert_eq!(paths.cost(), 4);
+        assert_eq!(paths.count(), 2);
+        assert_eq!(paths.goals().copied().collect::<Vec<_>>().len(), 2);
+        assert!(!paths.states().contains(&&p2!(3, 1)));
+
+        let paths = dijkstra_all(
+            &[p2!(0, 0), p2!(0, 2)],
+            |p: &GPoint| *p == p2!(3, 1),
+            |&p| open_neighbors(&grid, p),
+        )
+        .unwrap();
+
+        assert_eq!(paths.cost(), 4);
+        assert_eq!(paths.count(), 2);
+
+        assert!(dijkstra_all(
+            &[p2!(0, 0)],
+            |&p: &GPoint| p == p2!(1, 1),
+            |&p| open_neighbors(&grid, p)
+        )
+        .is_none());
+    }
+}
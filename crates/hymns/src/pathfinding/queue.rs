@@ -0,0 +1,120 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+// The open set of a search. Entries are never updated in place: a state whose score
+// improves is pushed again, and the stale entry is skipped when it's popped.
+pub(crate) trait Frontier<C> {
+    fn push(&mut self, priority: C, id: usize, g_score: C);
+
+    fn pop(&mut self) -> Option<(usize, C)>;
+
+    fn len(&self) -> usize;
+}
+
+pub(crate) struct HeapQueue<C> {
+    heap: BinaryHeap<(Reverse<C>, usize, C)>,
+}
+
+impl<C> HeapQueue<C>
+where
+    C: Ord,
+{
+    pub(crate) fn new() -> Self {
+        Self {
+            heap: BinaryHeap::new(),
+        }
+    }
+}
+
+impl<C> Frontier<C> for HeapQueue<C>
+where
+    C: Ord,
+{
+    fn push(&mut self, priority: C, id: usize, g_score: C) {
+        self.heap.push((Reverse(priority), id, g_score));
+    }
+
+    fn pop(&mut self) -> Option<(usize, C)> {
+        self.heap.pop().map(|(_, id, g_score)| (id, g_score))
+    }
+
+    fn len(&self) -> usize {
+        self.heap.len()
+    }
+}
+
+// A monotone priority queue for small integer priorities: one bucket per priority and a
+// cursor that only moves back if something is pushed below it.
+pub(crate) struct BucketQueue {
+    buckets: Vec<Vec<(usize, usize)>>,
+    cursor: usize,
+    len: usize,
+}
+
+impl BucketQueue {
+    pub(crate) fn new() -> Self {
+        Self {
+            buckets: vec![],
+            cursor: 0,
+            len: 0,
+        }
+    }
+}
+
+impl Frontier<usize> for BucketQueue {
+    fn push(&mut self, priority: usize, id: usize, g_score: usize) {
+        if priority >= self.buckets.len() {
+            self.buckets.resize_with(priority + 1, Vec::new);
+        }
+
+        self.buckets[priority].push((id, g_score));
+        self.cursor = self.cursor.min(priority);
+        self.len += 1;
+    }
+
+    fn pop(&mut self) -> Option<(usize, usize)> {
+        if self.len == 0 {
+            return None;
+        }
+
+        while self.buckets[self.cursor].is_empty() {
+            self.cursor += 1;
+        }
+
+        self.len -= 1;
+        self.buckets[self.cursor].pop()
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drain(queue: &mut impl Frontier<usize>) -> Vec<usize> {
+        std::iter::from_fn(|| queue.pop().map(|(id, _)| id)).collect()
+    }
+
+    #[test]
+    fn test_queues() {
+        let mut heap = HeapQueue::new();
+        let mut buckets = BucketQueue::new();
+
+        for queue in [&mut heap as &mut dyn Frontier<usize>, &mut buckets] {
+            queue.push(5, 0, 0);
+            queue.push(2, 1, 0);
+            queue.push(9, 2, 0);
+            assert_eq!(queue.pop(), Some((1, 0)));
+
+            queue.push(1, 3, 7);
+            assert_eq!(queue.pop(), Some((3, 7)));
+        }
+
+        assert_eq!(drain(&mut heap), vec![0, 2]);
+        assert_eq!(drain(&mut buckets), vec![0, 2]);
+        assert_eq!(buckets.pop(), None);
+    }
+}
@@ -0,0 +1,185 @@
+// Minimal PNG encoding: 8-bit RGB, uncompressed (stored) deflate blocks.
+
+use crate::render::Color;
+
+pub(crate) const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+const COLOR_TYPE_RGB: u8 = 2;
+
+pub(crate) fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = u32::MAX;
+
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+
+    !crc
+}
+
+pub(crate) fn adler32(bytes: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+
+    let (a, b) = bytes.iter().fold((1_u32, 0_u32), |(a, b), &byte| {
+        let a = (a + u32::from(byte)) % MOD;
+        (a, (b + a) % MOD)
+    });
+
+    (b << 16) | a
+}
+
+pub(crate) fn zlib_stored(bytes: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = 0xffff;
+
+    let mut out = Vec::with_capacity(bytes.len() + bytes.len() / MAX_BLOCK * 5 + 11);
+    out.extend([0x78, 0x01]);
+
+    let mut chunks = bytes.chunks(MAX_BLOCK).peekable();
+
+    if chunks.peek().is_none() {
+        out.extend([1, 0, 0, 0xff, 0xff]);
+    }
+
+    while let Some(chunk) = chunks.next() {
+        let len = u16::try_from(chunk.len()).unwrap();
+
+        out.push(u8::from(chunks.peek().is_none()));
+        out.extend(len.to_le_bytes());
+        out.extend((!len).to_le_bytes());
+        out.extend(chunk);
+    }
+
+    out.extend(adler32(bytes).to_be_bytes());
+    out
+}
+
+pub(crate) fn write_chunk(out: &mut Vec<u8>, kind: [u8; 4], data: &[u8]) {
+    out.extend(u32::try_from(data.len()).unwrap().to_be_bytes());
+
+    let start = out.len();
+    out.extend(kind);
+    out.extend(data);
+
+    let crc = crc32(&out[start..]);
+    out.extend(crc.to_be_bytes());
+}
+
+pub(crate) fn ihdr(width: usize, height: usize) -> Vec<u8> {
+    let mut data = Vec::with_capacity(13);
+    data.extend(u32::try_from(width).unwrap().to_be_bytes());
+    data.extend(u32::try_from(height).unwrap().to_be_bytes());
+    data.extend([8, COLOR_TYPE_RGB, 0, 0, 0]);
+    data
+}
+
+// Raw scanlines, each prefixed with the "None" filter type.
+pub(crate) fn rgb_scanlines<F>(width: usize, height: usize, pixel: F) -> Vec<u8>
+where
+    F: Fn(usize, usize) -> Color,
+{
+    let mut data = Vec::with_capacity(height * (width * 3 + 1));
+
+    for y in 0..height {
+        data.push(0);
+        for x in 0..width {
+            let Color(r, g, b) = pixel(x, y);
+            data.extend([r, g, b]);
+        }
+    }
+
+    data
+}
+
+#[cfg(test)]
+pub(crate) fn read_chunks(bytes: &[u8]) -> Vec<([u8; 4], Vec<u8>)> {
+    assert_eq!(bytes[..8], SIGNATURE);
+
+    let mut chunks = vec![];
+    let mut rest = &bytes[8..];
+
+    while !rest.is_empty() {
+        let len = u32::from_be_bytes(rest[..4].try_into().unwrap()) as usize;
+        let kind: [u8; 4] = rest[4..8].try_into().unwrap();
+        let data = rest[8..8 + len].to_vec();
+        let crc = u32::from_be_bytes(rest[8 + len..12 + len].try_into().unwrap());
+
+        assert_eq!(crc, crc32(&rest[4..8 + len]));
+
+        chunks.push((kind, data));
+        rest = &rest[12 + len..];
+    }
+
+    chunks
+}
+
+// Decodes a zlib stream made of stored blocks only.
+#[cfg(test)]
+pub(crate) fn unzlib_stored(bytes: &[u8]) -> Vec<u8> {
+    let mut out = vec![];
+    let mut rest = &bytes[2..];
+
+    loop {
+        let last = rest[0] & 1 == 1;
+        let len = usize::from(u16::from_le_bytes([rest[1], rest[2]]));
+        out.extend(&rest[5..5 + len]);
+        rest = &rest[5 + len..];
+
+        if last {
+            break;
+        }
+    }
+
+    assert_eq!(rest, adler32(&out).to_be_bytes());
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checksums() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+        assert_eq!(adler32(b""), 1);
+    }
+
+    #[test]
+    fn test_zlib_stored() {
+        assert_eq!(unzlib_stored(&zlib_stored(b"")), b"");
+        assert_eq!(unzlib_stored(&zlib_stored(b"hello")), b"hello");
+
+        let big = (0..200_000_u32)
+            .map(|i| (i % 251) as u8)
+            .collect::<Vec<_>>();
+        let compressed = zlib_stored(&big);
+        assert_eq!(compressed.len(), big.len() + 4 * 5 + 6);
+        assert_eq!(unzlib_stored(&compressed), big);
+    }
+
+    #[test]
+    fn test_chunks() {
+        let mut out = SIGNATURE.to_vec();
+        write_chunk(&mut out, *b"IHDR", &ihdr(3, 2));
+        write_chunk(&mut out, *b"IEND", &[]);
+
+        let chunks = read_chunks(&out);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(&chunks[0].0, b"IHDR");
+        assert_eq!(chunks[0].1, vec![0, 0, 0, 3, 0, 0, 0, 2, 8, 2, 0, 0, 0]);
+        assert_eq!(&chunks[1].0, b"IEND");
+    }
+
+    #[test]
+    fn test_scanlines() {
+        assert_eq!(
+            rgb_scanlines(2, 1, |x, _| Color(u8::try_from(x).unwrap(), 1, 2)),
+            vec![0, 0, 1, 2, 1, 1, 2]
+        );
+    }
+}
@@ -0,0 +1,300 @@
+use std::fmt::{Display, Write};
+use std::io::{stdout, IsTerminal};
+
+use num_traits::ToPrimitive;
+
+use crate::grid::{GPoint, Grid};
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Color(pub u8, pub u8, pub u8);
+
+impl Color {
+    pub const RED: Color = Color(220, 50, 47);
+    pub const GREEN: Color = Color(133, 153, 0);
+    pub const YELLOW: Color = Color(181, 137, 0);
+    pub const BLUE: Color = Color(38, 139, 210);
+    pub const MAGENTA: Color = Color(211, 54, 130);
+    pub const CYAN: Color = Color(42, 161, 152);
+    pub const WHITE: Color = Color(238, 232, 213);
+
+    // Maps `t` in `0.0..=1.0` onto a blue (cold) to red (hot) gradient.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn heat(t: f64) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        let channel = |from: f64, to: f64| (from + (to - from) * t).round() as u8;
+
+        Color(
+            channel(0.0, 255.0),
+            channel(64.0, 32.0),
+            channel(255.0, 0.0),
+        )
+    }
+}
+
+// Maps values onto `0.0..=1.0` relative to the smallest and largest of `values`.
+pub(crate) fn heat_scale(values: impl IntoIterator<Item = f64>) -> impl Fn(f64) -> f64 {
+    let (min, max) = values
+        .into_iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+            (min.min(v), max.max(v))
+        });
+    let range = max - min;
+
+    move |val| {
+        if range > 0.0 {
+            (val - min) / range
+        } else {
+            0.0
+        }
+    }
+}
+
+// `text` is a single character, or a right-aligned value padded to the width of the
+// widest value in the grid.
+#[derive(Clone, Default)]
+struct Cell {
+    text: String,
+    fg: Option<Color>,
+    bg: Option<Color>,
+}
+
+#[derive(Clone)]
+pub struct Canvas {
+    cells: Grid<Cell>,
+}
+
+impl Canvas {
+    // Draws each value with its `Display` impl. Values that render wider than one
+    // character get every column padded to the widest one, so the cells stay aligned.
+    #[must_use]
+    pub fn from_grid<T: Display>(grid: &Grid<T>) -> Self {
+        let texts = grid
+            .iter_rows()
+            .map(|row| row.iter().map(ToString::to_string).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let width = texts
+            .iter()
+            .flatten()
+            .map(|text| text.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let cells = texts
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|text| Cell {
+                        text: if width > 1 {
+                            format!("{text:>width$} ")
+                        } else {
+                            text
+                        },
+                        ..Cell::default()
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            cells: Grid::new(cells),
+        }
+    }
+
+    // Draws `on` at each point and `off` everywhere else in the bounding box of the
+    // points and the origin.
+    pub fn from_points<'a>(
+        points: impl IntoIterator<Item = &'a GPoint>,
+        on: char,
+        off: char,
+    ) -> Self {
+        let points = points.into_iter().collect::<Vec<_>>();
+
+        let cols = points.iter().map(|p| p.x + 1).max().unwrap_or(0);
+        let rows = points.iter().map(|p| p.y + 1).max().unwrap_or(0);
+
+        let mut cells = Grid::new(vec![
+            vec![
+                Cell {
+                    text: off.to_string(),
+                    ..Cell::default()
+                };
+                cols
+            ];
+            rows
+        ]);
+
+        for p in points {
+            cells[p].text = on.to_string();
+        }
+
+        Self { cells }
+    }
+
+    #[must_use]
+    pub fn highlight<'a>(
+        mut self,
+        points: impl IntoIterator<Item = &'a GPoint>,
+        color: Color,
+    ) -> Self {
+        for p in points {
+            if let Some(cell) = self.cells.get_value_mut(p) {
+                cell.fg = Some(color);
+            }
+        }
+
+        self
+    }
+
+    #[must_use]
+    pub fn path(mut self, path: &[GPoint], color: Color) -> Self {
+        for p in path {
+            if let Some(cell) = self.cells.get_value_mut(p) {
+                cell.bg = Some(color);
+            }
+        }
+
+        self
+    }
+
+    // Colors the background of each cell by its value, scaled between the smallest
+    // and largest value given.
+    #[must_use]
+    pub fn heat<N: ToPrimitive>(mut self, values: impl IntoIterator<Item = (GPoint, N)>) -> Self {
+        let values = values
+            .into_iter()
+            .filter_map(|(p, val)| val.to_f64().map(|val| (p, val)))
+            .collect::<Vec<_>>();
+        let scale = heat_scale(values.iter().map(|&(_, v)| v));
+
+        for &(p, val) in &values {
+            if let Some(cell) = self.cells.get_value_mut(&p) {
+                cell.bg = Some(Color::heat(scale(val)));
+            }
+        }
+
+        self
+    }
+
+    #[must_use]
+    pub fn render_plain(&self) -> String {
+        let mut out = String::new();
+
+        for row in self.cells.iter_rows() {
+            out.extend(row.iter().map(|cell| cell.text.as_str()));
+            out.push('\n');
+        }
+
+        out
+    }
+
+    #[must_use]
+    pub fn render_ansi(&self) -> String {
+        let mut out = String::new();
+
+        for row in self.cells.iter_rows() {
+            for cell in row {
+                if let Some(Color(r, g, b)) = cell.fg {
+                    write!(out, "\x1b[1;38;2;{r};{g};{b}m").unwrap();
+                }
+                if let Some(Color(r, g, b)) = cell.bg {
+                    write!(out, "\x1b[48;2;{r};{g};{b}m").unwrap();
+                }
+
+                out.push_str(&cell.text);
+
+                if cell.fg.is_some() || cell.bg.is_some() {
+                    out.push_str("\x1b[0m");
+                }
+            }
+            out.push('\n');
+        }
+
+        out
+    }
+
+    // Uses ANSI colors only when stdout is a terminal.
+    #[must_use]
+    pub fn render(&self) -> String {
+        if stdout().is_terminal() {
+            self.render_ansi()
+        } else {
+            self.render_plain()
+        }
+    }
+
+    pub fn print(&self) {
+        print!("{}", self.render());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::p2;
+    use crate::vector2::Point2;
+
+    #[test]
+    fn test_plain() {
+        let grid: Grid<char> = "#.\n.#\n".parse().unwrap();
+
+        let canvas = Canvas::from_grid(&grid)
+            .highlight(&[p2!(0, 0)], Color::RED)
+            .path(&[p2!(1, 0), p2!(1, 1)], Color::BLUE);
+        assert_eq!(canvas.render_plain(), "#.\n.#\n");
+
+        let canvas = Canvas::from_points(&[p2!(0, 0), p2!(2, 1)], '#', ' ');
+        assert_eq!(canvas.render_plain(), "#  \n  #\n");
+
+        assert_eq!(Canvas::from_points(&[], '#', '.').render_plain(), "");
+    }
+
+    #[test]
+    fn test_wide_values() {
+        let grid = Grid::new(vec![vec![1, 10], vec![100, 7]]);
+
+        let canvas = Canvas::from_grid(&grid).highlight(&[p2!(1, 0)], Color(1, 2, 3));
+        assert_eq!(canvas.render_plain(), "  1  10 \n100   7 \n");
+        assert_eq!(
+            canvas.render_ansi(),
+            "  1 \x1b[1;38;2;1;2;3m 10 \x1b[0m\n100   7 \n"
+        );
+    }
+
+    #[test]
+    fn test_ansi() {
+        let grid = Grid::new(vec![vec![1, 2], vec![3, 4]]);
+
+        let canvas = Canvas::from_grid(&grid)
+            .highlight(&[p2!(0, 0), p2!(5, 5)], Color(1, 2, 3))
+            .path(&[p2!(1, 1)], Color(4, 5, 6));
+
+        assert_eq!(
+            canvas.render_ansi(),
+            concat!(
+                "\x1b[1;38;2;1;2;3m1\x1b[0m2\n",
+                "3\x1b[48;2;4;5;6m4\x1b[0m\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_heat() {
+        let grid = Grid::new(vec![vec![0_usize, 5, 10]]);
+
+        let canvas = Canvas::from_grid(&grid).heat(grid.iter_points_values().map(|(p, &v)| (p, v)));
+
+        assert_eq!(
+            canvas.cells.iter_values().map(|c| c.bg).collect::<Vec<_>>(),
+            vec![
+                Some(Color::heat(0.0)),
+                Some(Color::heat(0.5)),
+                Some(Color::heat(1.0))
+            ]
+        );
+        assert_eq!(Color::heat(0.0), Color(0, 64, 255));
+        assert_eq!(Color::heat(1.0), Color(255, 32, 0));
+        assert_eq!(Color::heat(7.0), Color::heat(1.0));
+    }
+}
@@ -0,0 +1,378 @@
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+
+use hashbrown::HashMap;
+
+#[derive(Debug, Default, Eq, PartialEq, Copy, Clone)]
+pub struct SearchStats {
+    // States taken off the frontier and processed.
+    pub expanded: usize,
+    // The most states waiting on the frontier at once.
+    pub max_frontier: usize,
+    // States reached again after they'd already been pushed, e.g. when a cheaper path
+    // to one turns up or bfs finds it a second time.
+    pub duplicates: usize,
+    // States skipped because their bound couldn't beat the best score so far.
+    pub pruned: usize,
+    // States skipped because an equivalent or better one was already seen.
+    pub dominated: usize,
+    pub elapsed: Duration,
+}
+
+type Observer<'a, State> = Box<dyn FnMut(&State) + 'a>;
+
+// Collects `SearchStats` while a search runs and hands every expanded state to an
+// optional observer, e.g. to draw the order a grid gets explored in. Stats add up
+// when a tracer is reused across searches.
+pub struct Tracer<'a, State> {
+    stats: SearchStats,
+    observer: Option<Observer<'a, State>>,
+}
+
+impl<State> Default for Tracer<'_, State> {
+    fn default() -> Self {
+        Self {
+            stats: SearchStats::default(),
+            observer: None,
+        }
+    }
+}
+
+impl<'a, State> Tracer<'a, State> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_observer(observer: impl FnMut(&State) + 'a) -> Self {
+        Self {
+            stats: SearchStats::default(),
+            observer: Some(Box::new(observer)),
+        }
+    }
+
+    #[must_use]
+    pub fn stats(&self) -> SearchStats {
+        self.stats
+    }
+
+    pub(crate) fn expand(&mut self, state: &State) {
+        self.stats.expanded += 1;
+
+        if let Some(observer) = &mut self.observer {
+            observer(state);
+        }
+    }
+
+    pub(crate) fn frontier(&mut self, len: usize) {
+        self.stats.max_frontier = self.stats.max_frontier.max(len);
+    }
+
+    pub(crate) fn duplicate(&mut self) {
+        self.stats.duplicates += 1;
+    }
+
+    pub(crate) fn time<R>(&mut self, run: impl FnOnce(&mut Self) -> R) -> R {
+        let start = Instant::now();
+        let result = run(self);
+        self.stats.elapsed += start.elapsed();
+        result
+    }
+}
+
+fn search<State, C, SuccessorFn, Successors, ScoreFn, BoundFn, DominatedFn>(
+    start: State,
+    get_successors: SuccessorFn,
+    score: ScoreFn,
+    bound: BoundFn,
+    mut is_dominated: DominatedFn,
+    tracer: &mut Tracer<State>,
+) -> C
+where
+    C: Copy + Ord,
+    SuccessorFn: Fn(&State) -> Successors,
+    Successors: IntoIterator<Item = State>,
+    ScoreFn: Fn(&State) -> C,
+    BoundFn: Fn(&State) -> C,
+    DominatedFn: FnMut(&State) -> bool,
+{
+    let mut best = score(&start);
+    let mut stack = vec![start];
+
+    while let Some(state) = stack.pop() {
+        if bound(&state) <= best {
+            tracer.stats.pruned += 1;
+            continue;
+        }
+
+        if is_dominated(&state) {
+            tracer.stats.dominated += 1;
+            continue;
+        }
+
+        tracer.expand(&state);
+
+        for next in get_successors(&state) {
+            best = best.max(score(&next));
+            stack.push(next);
+        }
+
+        tracer.frontier(stack.len());
+    }
+
+    best
+}
+
+// Maximizes `score` over every state reachable from `start`, exploring depth-first.
+// `bound` must never underestimate the best score reachable from a state, and any
+// state whose bound can't beat the best score seen so far is skipped with everything
+// below it.
+pub fn branch_and_bound<State, C, SuccessorFn, Successors, ScoreFn, BoundFn>(
+    start: State,
+    get_successors: SuccessorFn,
+    score: ScoreFn,
+    bound: BoundFn,
+) -> C
+where
+    C: Copy + Ord,
+    SuccessorFn: Fn(&State) -> Successors,
+    Successors: IntoIterator<Item = State>,
+    ScoreFn: Fn(&State) -> C,
+    BoundFn: Fn(&State) -> C,
+{
+    branch_and_bound_traced(start, get_successors, score, bound, &mut Tracer::new())
+}
+
+pub fn branch_and_bound_traced<State, C, SuccessorFn, Successors, ScoreFn, BoundFn>(
+    start: State,
+    get_successors: SuccessorFn,
+    score: ScoreFn,
+    bound: BoundFn,
+    tracer: &mut Tracer<State>,
+) -> C
+where
+    C: Copy + Ord,
+    SuccessorFn: Fn(&State) -> Successors,
+    Successors: IntoIterator<Item = State>,
+    ScoreFn: Fn(&State) -> C,
+    BoundFn: Fn(&State) -> C,
+{
+    tracer.time(|tracer| search(start, get_successors, score, bound, |_| false, tracer))
+}
+
+// Like `branch_and_bound`, but also skips states that are no better than one already
+// expanded. Only states with the same key are compared, and `dominates(a, b)` must only
+// hold if nothing reachable from `b` can beat the best reachable from `a`.
+pub fn branch_and_bound_dominated<
+    State,
+    C,
+    SuccessorFn,
+    Successors,
+    ScoreFn,
+    BoundFn,
+    Key,
+    KeyFn,
+    DominatesFn,
+>(
+    start: State,
+    get_successors: SuccessorFn,
+    score: ScoreFn,
+    bound: BoundFn,
+    get_key: KeyFn,
+    dominates: DominatesFn,
+) -> C
+where
+    State: Clone,
+    C: Copy + Ord,
+    SuccessorFn: Fn(&State) -> Successors,
+    Successors: IntoIterator<Item = State>,
+    ScoreFn: Fn(&State) -> C,
+    BoundFn: Fn(&State) -> C,
+    Key: Hash + Eq,
+    KeyFn: Fn(&State) -> Key,
+    DominatesFn: Fn(&State, &State) -> bool,
+{
+    branch_and_bound_dominated_traced(
+        start,
+        get_successors,
+        score,
+        bound,
+        get_key,
+        dominates,
+        &mut Tracer::new(),
+    )
+}
+
+pub fn branch_and_bound_dominated_traced<
+    State,
+    C,
+    SuccessorFn,
+    Successors,
+    ScoreFn,
+    BoundFn,
+    Key,
+    KeyFn,
+    DominatesFn,
+>(
+    start: State,
+    get_successors: SuccessorFn,
+    score: ScoreFn,
+    bound: BoundFn,
+    get_key: KeyFn,
+    dominates: DominatesFn,
+    tracer: &mut Tracer<State>,
+) -> C
+where
+    State: Clone,
+    C: Copy + Ord,
+    SuccessorFn: Fn(&State) -> Successors,
+    Successors: IntoIterator<Item = State>,
+    ScoreFn: Fn(&State) -> C,
+    BoundFn: Fn(&State) -> C,
+    Key: Hash + Eq,
+    KeyFn: Fn(&State) -> Key,
+    DominatesFn: Fn(&State, &State) -> bool,
+{
+    // the states expanded so far for each key that no other state dominates
+    let mut kept: HashMap<Key, Vec<State>> = HashMap::new();
+
+    let is_dominated = |state: &State| {
+        let others = kept.entry(get_key(state)).or_default();
+
+        if others.iter().any(|other| dominates(other, state)) {
+            return true;
+        }
+
+        others.retain(|other| !dominates(state, other));
+        others.push(state.clone());
+        false
+    };
+
+    tracer.time(|tracer| search(start, get_successors, score, bound, is_dominated, tracer))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // (weight, value)
+    const ITEMS: [(u32, u32); 8] = [
+        (12, 4),
+        (2, 2),
+        (1, 1),
+        (4, 10),
+        (1, 2),
+        (7, 13),
+        (5, 8),
+        (3, 3),
+    ];
+    const CAPACITY: u32 = 15;
+
+    #[derive(Debug, Clone)]
+    struct Knapsack {
+        next_item: usize,
+        weight: u32,
+        value: u32,
+    }
+
+    fn successors(state: &Knapsack) -> Vec<Knapsack> {
+        let Some(&(weight, value)) = ITEMS.get(state.next_item) else {
+            return vec![];
+        };
+
+        let skip = Knapsack {
+            next_item: state.next_item + 1,
+            ..state.clone()
+        };
+
+        if state.weight + weight > CAPACITY {
+            return vec![skip];
+        }
+
+        let take = Knapsack {
+            next_item: state.next_item + 1,
+            weight: state.weight + weight,
+            value: state.value + value,
+        };
+
+        vec![skip, take]
+    }
+
+    fn optimistic(state: &Knapsack) -> u32 {
+        state.value + ITEMS[state.next_item..].iter().map(|(_, v)| v).sum::<u32>()
+    }
+
+    fn best_by_brute_force() -> u32 {
+        (0_u32..1 << ITEMS.len())
+            .filter_map(|mask| {
+                let chosen = ITEMS
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| mask & (1 << i) != 0);
+                let (weight, value) =
+                    chosen.fold((0, 0), |(w, v), (_, item)| (w + item.0, v + item.1));
+                (weight <= CAPACITY).then_some(value)
+            })
+            .max()
+            .unwrap()
+    }
+
+    fn start() -> Knapsack {
+        Knapsack {
+            next_item: 0,
+            weight: 0,
+            value: 0,
+        }
+    }
+
+    #[test]
+    fn test_branch_and_bound() {
+        let mut tracer = Tracer::new();
+        let best =
+            branch_and_bound_traced(start(), successors, |s| s.value, optimistic, &mut tracer);
+        let stats = tracer.stats();
+
+        assert_eq!(best, best_by_brute_force());
+        assert!(stats.pruned > 0);
+        assert_eq!(stats.dominated, 0);
+
+        // without a useful bound every state gets expanded
+        let mut tracer = Tracer::new();
+        let best =
+            branch_and_bound_traced(start(), successors, |s| s.value, |_| u32::MAX, &mut tracer);
+        let exhaustive = tracer.stats();
+
+        assert_eq!(best, best_by_brute_force());
+        assert_eq!(exhaustive.pruned, 0);
+        assert!(exhaustive.max_frontier <= ITEMS.len() + 1);
+        assert!(exhaustive.expanded > stats.expanded);
+
+        assert_eq!(
+            branch_and_bound(start(), successors, |s| s.value, optimistic),
+            best
+        );
+    }
+
+    #[test]
+    fn test_dominated() {
+        // no bound, so only dominance cuts the search down
+        let mut plain = Tracer::new();
+        branch_and_bound_traced(start(), successors, |s| s.value, |_| u32::MAX, &mut plain);
+
+        let mut tracer = Tracer::new();
+        let best = branch_and_bound_dominated_traced(
+            start(),
+            successors,
+            |s| s.value,
+            |_| u32::MAX,
+            |s| s.next_item,
+            |a, b| a.weight <= b.weight && a.value >= b.value,
+            &mut tracer,
+        );
+        let (stats, plain_stats) = (tracer.stats(), plain.stats());
+
+        assert_eq!(best, best_by_brute_force());
+        assert!(stats.dominated > 0);
+        assert!(stats.expanded < plain_stats.expanded);
+    }
+}
@@ -175,7 +175,7 @@ impl<T: PrimInt + AddAssign> Point2<T> {
             neighbors.append(&mut self.get_neighbors(&EIGHT_NEIGHBORS));
         } else {
             neighbors.append(&mut self.get_neighbors(&FOUR_NEIGHBORS));
-        };
+        }
 
         if include_self {
             neighbors.push(*self);